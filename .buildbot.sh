@@ -18,6 +18,13 @@ ulimit -d $((1024 * 1024 * 8)) # 8 GiB
 
 ENABLE_GC_ASSERTIONS=true /usr/bin/time -v python3 x.py test --stage 2 --config .buildbot.config.toml --exclude rustdoc-json --exclude debuginfo
 
+# Rerun the GC tests with write barriers compiled in, so that incremental
+# collection is actually exercised.
+ALLOY_EXPECT_INCREMENTAL=true python3 x.py test --stage 2 \
+    --config .buildbot.config.toml \
+    --set alloy.gc-write-barriers=true \
+    tests/ui/runtime/gc
+
 # Install rustup
 
 curl --proto '=https' --tlsv1.2 -sSf https://sh.rustup.rs > rustup.sh
@@ -70,4 +70,17 @@ pub struct AllocatorMethodInput {
         inputs: &[AllocatorMethodInput { name: "layout", ty: AllocatorTy::Layout }],
         output: AllocatorTy::ResultPtr,
     },
+    AllocatorMethod {
+        name: sym::alloc_atomic,
+        inputs: &[AllocatorMethodInput { name: "layout", ty: AllocatorTy::Layout }],
+        output: AllocatorTy::ResultPtr,
+    },
+    AllocatorMethod {
+        name: sym::realloc_scanned,
+        inputs: &[
+            AllocatorMethodInput { name: "ptr", ty: AllocatorTy::Ptr },
+            AllocatorMethodInput { name: "layout", ty: AllocatorTy::Layout },
+        ],
+        output: AllocatorTy::ResultPtr,
+    },
 ];
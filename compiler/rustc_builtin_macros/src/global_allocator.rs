@@ -20,7 +20,7 @@ pub fn expand(
 ) -> Vec<Annotatable> {
     check_builtin_macro_attribute(ecx, meta_item, sym::global_allocator);
 
-    let orig_item = item.clone();
+    let mut orig_item = item.clone();
 
     // Allow using `#[global_allocator]` on an item statement
     // FIXME - if we get deref patterns, use them to reduce duplication here
@@ -40,6 +40,19 @@ pub fn expand(
 
     // Generate a bunch of new items using the AllocFnFactory
     let span = ecx.with_def_site_ctxt(item.span);
+
+    // Mark the static itself so that later stages can find out which type was
+    // registered as the global allocator.
+    let marker = ecx.attr_word(sym::rustc_global_allocator, span);
+    match &mut orig_item {
+        Annotatable::Item(item) => item.attrs.push(marker),
+        Annotatable::Stmt(stmt) => {
+            if let StmtKind::Item(item) = &mut stmt.kind {
+                item.attrs.push(marker)
+            }
+        }
+        _ => unreachable!(),
+    }
     let f = AllocFnFactory { span, ty_span, global: item.ident, cx: ecx };
 
     // Generate item statements for the allocator methods.
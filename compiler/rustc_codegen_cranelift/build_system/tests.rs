@@ -79,6 +79,35 @@ const fn jit_bin(config: &'static str, source: &'static str, args: &'static str)
     TestCase::build_bin_and_run("aot.alloc_example", "example/alloc_example.rs", &[]),
     TestCase::jit_bin("jit.std_example", "example/std_example.rs", ""),
     TestCase::build_bin_and_run("aot.std_example", "example/std_example.rs", &["arg"]),
+    TestCase::jit_bin("jit.gc_example", "example/gc_example.rs", ""),
+    TestCase::build_bin_and_run("aot.gc_example", "example/gc_example.rs", &[]),
+    TestCase::custom("aot.gc_ui_tests", &|runner| {
+        // Alloy's `Gc` run-pass tests live in the enclosing rust tree, not in the sysroot source.
+        let dir = RelPath::SOURCE.to_path(&runner.dirs).join("../../tests/ui/runtime/gc");
+        let mut tests = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension() == Some(OsStr::new("rs")))
+            .collect::<Vec<_>>();
+        tests.sort();
+        for test in tests {
+            let name = test.file_stem().unwrap().to_str().unwrap();
+            let source = fs::read_to_string(&test).unwrap();
+            // Tests which need anything beyond a plain build and run (extra flags, an expected
+            // failure, ...) are left to compiletest.
+            if !source.lines().any(|line| line == "//@ run-pass")
+                || source.lines().any(|line| {
+                    line.starts_with("//@ ignore-test") || line.starts_with("//@ compile-flags")
+                })
+            {
+                eprintln!("[AOT] gc_ui_tests/{name} (skipped)");
+                continue;
+            }
+            eprintln!("[AOT] gc_ui_tests/{name}");
+            runner.run_rustc([test.to_str().unwrap()]);
+            runner.run_out_command(name, &[]);
+        }
+    }),
     TestCase::build_bin_and_run("aot.dst_field_align", "example/dst-field-align.rs", &[]),
     TestCase::build_bin_and_run(
         "aot.subslice-patterns-const-eval",
@@ -0,0 +1,54 @@
+#![feature(allocator_api, core_intrinsics, gc)]
+
+use std::gc::{Gc, GcAllocator};
+use std::intrinsics::{gc_keep_alive, needs_finalizer};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+static FINALIZED: AtomicUsize = AtomicUsize::new(0);
+
+const ALLOCATED: usize = 100;
+
+struct Finalizable(usize);
+
+impl Drop for Finalizable {
+    fn drop(&mut self) {
+        FINALIZED.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[inline(never)]
+fn allocate() {
+    for i in 0..ALLOCATED {
+        let gc = Gc::new(Finalizable(i));
+        assert_eq!(gc.0, i);
+        gc_keep_alive(Gc::as_ptr(&gc) as *const u8);
+    }
+}
+
+fn main() {
+    assert!(needs_finalizer::<Finalizable>());
+    assert!(!needs_finalizer::<usize>());
+    assert!(!needs_finalizer::<Gc<Finalizable>>());
+
+    // The buffer must come from the collector, otherwise it isn't scanned for roots.
+    let mut list = Vec::with_capacity_in(ALLOCATED, GcAllocator);
+    list.extend((0..ALLOCATED).map(Gc::new));
+    GcAllocator::force_gc();
+    assert_eq!(list.iter().map(|gc| **gc).sum::<usize>(), 4950);
+
+    allocate();
+    GcAllocator::force_gc();
+
+    // Finalizers run on a separate thread, so give it some time to catch up.
+    let mut sleep = 2;
+    while FINALIZED.load(Ordering::Relaxed) < ALLOCATED - 1 && sleep <= 8192 {
+        thread::sleep(Duration::from_millis(sleep));
+        sleep *= 2;
+    }
+    // The last object may still be found through a stale stack slot.
+    let finalized = FINALIZED.load(Ordering::Relaxed);
+    assert!(finalized >= ALLOCATED - 1, "only {finalized} objects were finalized");
+    assert!(finalized <= ALLOCATED);
+}
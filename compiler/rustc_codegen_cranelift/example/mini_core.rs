@@ -595,7 +595,7 @@ fn deref(&self) -> &Self::Target {
 impl<T: NoFinalize> NoFinalize for Box<T> {}
 
 #[lang = "exchange_malloc"]
-unsafe fn allocate(size: usize, _align: usize) -> *mut u8 {
+unsafe fn allocate<T>(size: usize, _align: usize) -> *mut u8 {
     libc::malloc(size)
 }
 
@@ -5,7 +5,7 @@
     alloc_error_handler_name, default_fn_name, global_fn_name, AllocatorKind, AllocatorTy,
     ALLOCATOR_METHODS, NO_ALLOC_SHIM_IS_UNSTABLE,
 };
-use rustc_codegen_ssa::base::allocator_kind_for_codegen;
+use rustc_codegen_ssa::base::{allocator_kind_for_codegen, check_gc_allocator};
 use rustc_session::config::OomStrategy;
 
 use crate::prelude::*;
@@ -17,6 +17,7 @@ pub(crate) fn codegen(
     unwind_context: &mut UnwindContext,
 ) -> bool {
     let Some(kind) = allocator_kind_for_codegen(tcx) else { return false };
+    check_gc_allocator(tcx);
     codegen_inner(
         module,
         unwind_context,
@@ -310,6 +310,7 @@ fn dep_symbol_lookup_fn(
     crate_info: CrateInfo,
 ) -> Box<dyn Fn(&str) -> Option<*const u8>> {
     use rustc_middle::middle::dependency_format::Linkage;
+    use rustc_session::utils::NativeLibKind;
 
     let mut dylib_paths = Vec::new();
 
@@ -339,10 +340,36 @@ fn dep_symbol_lookup_fn(
         }
     }
 
+    // Native libraries of crates included in a dylib are either linked into that dylib already
+    // (static libraries, like BDWGC in the standard library), in which case their symbols are
+    // found through it, or loaded here where possible. Dynamic native libraries that can't be
+    // found by name are usually reachable through the dylibs depending on them.
+    let mut native_dylibs = Vec::new();
+    for &cnum in &crate_info.used_crates {
+        if data[cnum.as_usize() - 1] != Linkage::IncludedFromDylib {
+            continue;
+        }
+        // FIXME evaluate the `cfg` of conditionally linked libraries
+        for lib in crate_info.native_libraries[&cnum].iter().filter(|lib| lib.cfg.is_none()) {
+            if let NativeLibKind::Dylib { .. } | NativeLibKind::Unspecified = lib.kind {
+                native_dylibs.push(if lib.verbatim {
+                    lib.name.as_str().into()
+                } else {
+                    libloading::library_filename(lib.name.as_str())
+                });
+            }
+        }
+    }
+
     let imported_dylibs = Box::leak(
         dylib_paths
             .into_iter()
             .map(|path| unsafe { libloading::Library::new(&path).unwrap() })
+            .chain(
+                native_dylibs
+                    .into_iter()
+                    .filter_map(|name| unsafe { libloading::Library::new(name).ok() }),
+            )
             .collect::<Box<[_]>>(),
     );
 
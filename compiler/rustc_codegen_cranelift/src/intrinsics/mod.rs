@@ -728,6 +728,9 @@ fn codegen_regular_intrinsic_call<'tcx>(
 
         sym::pref_align_of
         | sym::needs_drop
+        | sym::needs_finalizer
+        | sym::needs_finalizer_with_gc_allocator
+        | sym::may_contain_pointers
         | sym::type_id
         | sym::type_name
         | sym::variant_count => {
@@ -1241,6 +1244,16 @@ fn codegen_regular_intrinsic_call<'tcx>(
             ret.write_cvalue(fx, CValue::by_val(cmp, ret.layout()));
         }
 
+        sym::gc_keep_alive => {
+            intrinsic_args!(fx, args => (ptr); intrinsic);
+
+            // Spill the pointer to the stack, where the collector will find it
+            // for at least as long as this frame is live. Cranelift treats
+            // stores as volatile, so the spill can't be optimized away.
+            let slot = CPlace::new_stack_slot(fx, ptr.layout());
+            slot.write_cvalue(fx, ptr);
+        }
+
         sym::black_box => {
             intrinsic_args!(fx, args => (a); intrinsic);
 
@@ -54,6 +54,20 @@ fn prepare_libcore(
         None,
     )?;
 
+    // The `bdwgc` crate builds BDWGC from `src/bdwgc`, relative to `library`.
+    let sysroot_bdwgc_dir = sysroot_dir.join("src");
+    fs::create_dir_all(&sysroot_bdwgc_dir).map_err(|error| {
+        format!(
+            "Failed to create folder `{}`: {:?}",
+            sysroot_bdwgc_dir.display(),
+            error,
+        )
+    })?;
+    run_command(
+        &[&"cp", &"-r", &rustlib_dir.join("src/bdwgc"), &sysroot_bdwgc_dir],
+        None,
+    )?;
+
     println!("[GIT] init (cwd): `{}`", sysroot_dir.display());
     run_command(&[&"git", &"init"], Some(&sysroot_dir))?;
     println!("[GIT] add (cwd): `{}`", sysroot_dir.display());
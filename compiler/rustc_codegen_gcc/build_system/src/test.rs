@@ -408,6 +408,35 @@ fn std_tests(env: &Env, args: &TestArg) -> Result<(), String> {
         args,
     )?;
 
+    // FIXME: create a function "display_if_not_quiet" or something along the line.
+    println!("[AOT] gc_example");
+    let mut command = args.config_info.rustc_command_vec();
+    command.extend_from_slice(&[
+        &"example/gc_example.rs",
+        &"--crate-type",
+        &"bin",
+        &"-O",
+        &"--target",
+        &args.config_info.target_triple,
+    ]);
+    run_command_with_env(&command, None, Some(env))?;
+    let gc_example = cargo_target_dir.join("gc_example");
+    // BDWGC is linked into std statically unless `GC_LINK_DYNAMIC` is set, in
+    // which case the collector must come from `libgc.so` instead.
+    let symbols = run_command(&[&"nm", &"--defined-only", &gc_example], None)?;
+    let defines_gc = String::from_utf8_lossy(&symbols.stdout)
+        .lines()
+        .any(|line| line.ends_with(" GC_malloc"));
+    let link_dynamic = env.get("GC_LINK_DYNAMIC").is_some_and(|value| value == "true");
+    if defines_gc == link_dynamic {
+        return Err(format!(
+            "`{}` should {}define `GC_malloc` itself",
+            gc_example.display(),
+            if link_dynamic { "not " } else { "" },
+        ));
+    }
+    maybe_run_command_in_vm(&[&gc_example], env, args)?;
+
     let test_flags = if let Some(test_flags) = env.get("TEST_FLAGS") {
         split_args(test_flags)?
     } else {
@@ -0,0 +1,94 @@
+#![feature(core_intrinsics, gc)]
+
+// Exercises the parts of Alloy which the GCC backend lowers itself: the
+// `gc_keep_alive` intrinsic, which must keep its operand somewhere the
+// collector scans, and `needs_finalizer`.
+
+use std::gc::{Gc, GcAllocator};
+use std::intrinsics::{gc_keep_alive, needs_finalizer};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+static FINALIZED: AtomicUsize = AtomicUsize::new(0);
+
+const LEN: usize = 10;
+const ROUNDS: usize = 10;
+
+struct Node {
+    id: usize,
+    next: Option<Gc<Node>>,
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {
+        FINALIZED.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+trait Value {
+    fn value(&self) -> usize;
+}
+
+impl Value for Node {
+    fn value(&self) -> usize {
+        self.id
+    }
+}
+
+#[inline(never)]
+fn chain(len: usize) -> Gc<Node> {
+    let mut head = Gc::new(Node { id: 0, next: None });
+    for id in 1..len {
+        head = Gc::new(Node { id, next: Some(head) });
+    }
+    head
+}
+
+/// Walks the list through raw pointers, so only the caller's barrier keeps it alive.
+#[inline(never)]
+fn sum(mut node: *const Node) -> usize {
+    let mut sum = 0;
+    loop {
+        let current = unsafe { &*node };
+        sum += current.id;
+        match current.next {
+            Some(next) => node = Gc::as_ptr(&next),
+            None => return sum,
+        }
+    }
+}
+
+fn wait_for_finalizers(expected: usize) -> usize {
+    let mut sleep = 2;
+    while FINALIZED.load(Ordering::Relaxed) < expected && sleep <= 8192 {
+        thread::sleep(Duration::from_millis(sleep));
+        sleep *= 2;
+    }
+    FINALIZED.load(Ordering::Relaxed)
+}
+
+fn main() {
+    assert!(needs_finalizer::<Node>());
+    assert!(!needs_finalizer::<Vec<Gc<Node>>>());
+
+    let head = Gc::as_ptr(&chain(LEN));
+    GcAllocator::force_gc();
+    thread::sleep(Duration::from_millis(50));
+    assert_eq!(sum(head), LEN * (LEN - 1) / 2);
+    assert_eq!(FINALIZED.load(Ordering::Relaxed), 0, "a reachable node was finalized");
+    gc_keep_alive(head as *const u8);
+
+    let value: Gc<dyn Value> = chain(LEN);
+    assert_eq!(value.value(), LEN - 1);
+    gc_keep_alive(Gc::as_ptr(&value) as *const u8);
+
+    for _ in 0..ROUNDS {
+        chain(LEN);
+    }
+    GcAllocator::force_gc();
+    // The most recent chains may still be found through stale stack slots.
+    let finalized = wait_for_finalizers((ROUNDS - 1) * LEN);
+    assert!(finalized >= (ROUNDS - 1) * LEN, "only {finalized} nodes were finalized");
+    assert!(finalized <= (ROUNDS + 2) * LEN);
+}
@@ -557,7 +557,7 @@ fn deref(&self) -> &Self::Target {
 }
 
 #[lang = "exchange_malloc"]
-unsafe fn allocate(size: usize, _align: usize) -> *mut u8 {
+unsafe fn allocate<T>(size: usize, _align: usize) -> *mut u8 {
     libc::malloc(size)
 }
 
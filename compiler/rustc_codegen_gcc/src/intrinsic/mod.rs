@@ -439,6 +439,16 @@ fn va_start(&mut self, _va_list: RValue<'gcc>) -> RValue<'gcc> {
     fn va_end(&mut self, _va_list: RValue<'gcc>) -> RValue<'gcc> {
         unimplemented!();
     }
+
+    fn gc_keep_alive(&mut self, ptr: RValue<'gcc>) {
+        // `asm volatile ("" :: "r"(ptr))`: GCC must materialize `ptr` in a register here and may
+        // not delete the asm, even though it has no outputs. There is deliberately no `memory`
+        // clobber, so loads and stores can still move across the barrier.
+        let block = self.llbb();
+        let extended_asm = block.add_extended_asm(None, "");
+        extended_asm.add_input_operand(None, "r", ptr);
+        extended_asm.set_volatile_flag(true);
+    }
 }
 
 impl<'a, 'gcc, 'tcx> ArgAbiMethods<'tcx> for Builder<'a, 'gcc, 'tcx> {
@@ -17,7 +17,7 @@
 use rustc_middle::ty::layout::{FnAbiOf, HasTyCtxt, LayoutOf};
 use rustc_middle::ty::{self, GenericArgsRef, Ty};
 use rustc_middle::{bug, span_bug};
-use rustc_span::{sym, Span, Symbol};
+use rustc_span::{sym, Span, Symbol, DUMMY_SP};
 use rustc_target::abi::{self, Align, HasDataLayout, Primitive};
 use rustc_target::spec::{HasTargetSpec, PanicStrategy};
 
@@ -529,6 +529,28 @@ fn va_start(&mut self, va_list: &'ll Value) -> &'ll Value {
     fn va_end(&mut self, va_list: &'ll Value) -> &'ll Value {
         self.call_intrinsic("llvm.va_end", &[va_list])
     }
+
+    fn gc_keep_alive(&mut self, ptr: &'ll Value) {
+        // An empty asm block which takes the pointer in a register. Unlike `black_box`, this
+        // doesn't clobber memory, so the only thing LLVM has to preserve is the pointer's value
+        // up to this point.
+        crate::asm::inline_asm_call(
+            self,
+            "",
+            "r",
+            &[ptr],
+            self.type_void(),
+            &[],
+            true,
+            false,
+            llvm::AsmDialect::Att,
+            &[DUMMY_SP],
+            false,
+            None,
+            None,
+        )
+        .unwrap_or_else(|| bug!("failed to generate inline asm call for `gc_keep_alive`"));
+    }
 }
 
 fn catch_unwind_intrinsic<'ll>(
@@ -47,6 +47,10 @@ codegen_ssa_failed_to_write = failed to write {$path}: {$error}
 
 codegen_ssa_field_associated_value_expected = associated value expected for `{$name}`
 
+codegen_ssa_gc_without_gc_allocator = `Gc` is used without `GcAllocator` as the global allocator
+    .note = `Gc` is used by {$crates}
+    .help = add `#[global_allocator] static A: std::gc::GcAllocator = std::gc::GcAllocator;`
+
 codegen_ssa_ignoring_emit_path = ignoring emit path because multiple .{$extension} files were produced
 
 codegen_ssa_ignoring_output = ignoring -o because multiple .{$extension} files were produced
@@ -23,6 +23,7 @@
 use rustc_hir as hir;
 use rustc_hir::def_id::{DefId, LOCAL_CRATE};
 use rustc_hir::lang_items::LangItem;
+use rustc_hir::CRATE_HIR_ID;
 use rustc_metadata::EncodedMetadata;
 use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrs;
 use rustc_middle::middle::debugger_visualizer::{DebuggerVisualizerFile, DebuggerVisualizerType};
@@ -34,6 +35,7 @@
 use rustc_middle::ty::layout::{HasTyCtxt, LayoutOf, TyAndLayout};
 use rustc_middle::ty::{self, Instance, Ty, TyCtxt};
 use rustc_session::config::{self, CrateType, EntryFnType, OptLevel, OutputType};
+use rustc_session::lint::builtin::GC_WITHOUT_GC_ALLOCATOR;
 use rustc_session::Session;
 use rustc_span::symbol::sym;
 use rustc_span::Symbol;
@@ -41,6 +43,7 @@
 
 use std::cmp;
 use std::collections::BTreeSet;
+use std::iter;
 use std::time::{Duration, Instant};
 
 use itertools::Itertools;
@@ -568,6 +571,27 @@ pub fn allocator_kind_for_codegen(tcx: TyCtxt<'_>) -> Option<AllocatorKind> {
     if any_dynamic_crate { None } else { tcx.allocator_kind(()) }
 }
 
+/// Warn if a crate in the graph uses `Gc` but the allocator shim being
+/// generated does not forward to `GcAllocator`. Objects allocated by any other
+/// global allocator are not scanned, so `Gc`s stored in them are lost.
+pub fn check_gc_allocator(tcx: TyCtxt<'_>) {
+    if tcx.gc_is_global_allocator() {
+        return;
+    }
+    let crates: Vec<Symbol> = iter::once(LOCAL_CRATE)
+        .chain(tcx.crates(()).iter().copied())
+        .filter(|&cnum| tcx.crate_uses_gc(cnum))
+        .map(|cnum| tcx.crate_name(cnum))
+        .collect();
+    if !crates.is_empty() {
+        tcx.emit_node_lint(
+            GC_WITHOUT_GC_ALLOCATOR,
+            CRATE_HIR_ID,
+            errors::GcWithoutGcAllocator { crates: crates.into() },
+        );
+    }
+}
+
 pub fn codegen_crate<B: ExtraBackendMethods>(
     backend: B,
     tcx: TyCtxt<'_>,
@@ -635,6 +659,7 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
 
     // Codegen an allocator shim, if necessary.
     if let Some(kind) = allocator_kind_for_codegen(tcx) {
+        check_gc_allocator(tcx);
         let llmod_id =
             cgu_name_builder.build_cgu_name(LOCAL_CRATE, &["crate"], Some("allocator")).to_string();
         let module_llvm = tcx.sess.time("write_allocator_module", || {
@@ -4,9 +4,10 @@
 use crate::back::command::Command;
 use crate::fluent_generated as fluent;
 use rustc_errors::{
-    codes::*, Diag, DiagArgValue, DiagCtxt, Diagnostic, EmissionGuarantee, IntoDiagArg, Level,
+    codes::*, Diag, DiagArgValue, DiagCtxt, DiagSymbolList, Diagnostic, EmissionGuarantee,
+    IntoDiagArg, Level,
 };
-use rustc_macros::Diagnostic;
+use rustc_macros::{Diagnostic, LintDiagnostic};
 use rustc_middle::ty::layout::LayoutError;
 use rustc_middle::ty::Ty;
 use rustc_span::{Span, Symbol};
@@ -1037,3 +1038,11 @@ pub struct CompilerBuiltinsCannotCall {
     pub caller: String,
     pub callee: String,
 }
+
+#[derive(LintDiagnostic)]
+#[diag(codegen_ssa_gc_without_gc_allocator)]
+#[note]
+#[help]
+pub struct GcWithoutGcAllocator {
+    pub crates: DiagSymbolList,
+}
@@ -108,6 +108,11 @@ pub fn codegen_intrinsic_call(
                 return Ok(());
             }
 
+            sym::gc_keep_alive => {
+                bx.gc_keep_alive(args[0].immediate());
+                return Ok(());
+            }
+
             sym::va_start => bx.va_start(args[0].immediate()),
             sym::va_end => bx.va_end(args[0].immediate()),
             sym::size_of_val => {
@@ -149,6 +154,8 @@ pub fn codegen_intrinsic_call(
             sym::pref_align_of
             | sym::needs_drop
             | sym::needs_finalizer
+            | sym::needs_finalizer_with_gc_allocator
+            | sym::may_contain_pointers
             | sym::type_id
             | sym::type_name
             | sym::variant_count => {
@@ -38,4 +38,7 @@ fn type_checked_load(
     /// Trait method used to inject `va_end` on the "spoofed" `VaListImpl` before
     /// Rust defined C-variadic functions return.
     fn va_end(&mut self, val: Self::Value) -> Self::Value;
+    /// Trait method used to keep a GC-allocated pointer visible to the collector up to this
+    /// point, without any other effect. Used by the `gc_keep_alive` intrinsic.
+    fn gc_keep_alive(&mut self, ptr: Self::Value);
 }
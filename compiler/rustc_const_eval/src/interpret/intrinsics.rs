@@ -58,6 +58,14 @@ pub(crate) fn eval_nullary_intrinsic<'tcx>(
             ensure_monomorphic_enough(tcx, tp_ty)?;
             ConstValue::from_bool(tp_ty.needs_finalizer(tcx, param_env))
         }
+        sym::needs_finalizer_with_gc_allocator => {
+            ensure_monomorphic_enough(tcx, tp_ty)?;
+            ConstValue::from_bool(tp_ty.needs_finalizer_with_gc_allocator(tcx, param_env))
+        }
+        sym::may_contain_pointers => {
+            ensure_monomorphic_enough(tcx, tp_ty)?;
+            ConstValue::from_bool(tp_ty.may_contain_pointers(tcx, param_env))
+        }
         sym::pref_align_of => {
             // Correctly handles non-monomorphic calls, so there is no need for ensure_monomorphic_enough.
             let layout = tcx.layout_of(param_env.and(tp_ty)).map_err(|e| err_inval!(Layout(*e)))?;
@@ -151,6 +159,8 @@ pub fn emulate_intrinsic(
             sym::pref_align_of
             | sym::needs_drop
             | sym::needs_finalizer
+            | sym::needs_finalizer_with_gc_allocator
+            | sym::may_contain_pointers
             | sym::type_id
             | sym::type_name
             | sym::variant_count => {
@@ -160,7 +170,9 @@ pub fn emulate_intrinsic(
                     sym::needs_drop => self.tcx.types.bool,
                     sym::type_id => self.tcx.types.u128,
                     sym::type_name => Ty::new_static_str(self.tcx.tcx),
-                    sym::needs_finalizer => self.tcx.types.bool,
+                    sym::needs_finalizer
+                    | sym::needs_finalizer_with_gc_allocator
+                    | sym::may_contain_pointers => self.tcx.types.bool,
                     _ => bug!(),
                 };
                 let val =
@@ -420,6 +432,10 @@ pub fn emulate_intrinsic(
                 // These just return their argument
                 self.copy_op(&args[0], dest)?;
             }
+            sym::gc_keep_alive => {
+                // Only affects what the optimizer may assume about liveness, so
+                // there is nothing to do here.
+            }
             sym::raw_eq => {
                 let result = self.raw_eq_intrinsic(&args[0], &args[1])?;
                 self.write_scalar(result, dest)?;
@@ -458,6 +458,12 @@ fn run_compiler(
                 sess.code_stats.print_vtable_sizes(crate_name);
             }
 
+            if sess.opts.unstable_opts.print_gc_report {
+                let crate_name = queries.global_ctxt()?.enter(|tcx| tcx.crate_name(LOCAL_CRATE));
+
+                sess.code_stats.print_gc_report(crate_name, sess.source_map());
+            }
+
             Ok(Some(linker))
         })?;
 
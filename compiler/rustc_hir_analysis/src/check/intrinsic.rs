@@ -92,6 +92,9 @@ pub fn intrinsic_operation_unsafety(tcx: TyCtxt<'_>, intrinsic_id: LocalDefId) -
         | sym::min_align_of
         | sym::needs_drop
         | sym::needs_finalizer
+        | sym::needs_finalizer_with_gc_allocator
+        | sym::may_contain_pointers
+        | sym::gc_keep_alive
         | sym::caller_location
         | sym::add_with_overflow
         | sym::sub_with_overflow
@@ -249,7 +252,13 @@ pub fn check_intrinsic_type(
 
             sym::type_name => (1, 0, vec![], Ty::new_static_str(tcx)),
             sym::type_id => (1, 0, vec![], tcx.types.u128),
-            sym::needs_finalizer => (1, 0, vec![], tcx.types.bool),
+            sym::needs_finalizer | sym::needs_finalizer_with_gc_allocator => {
+                (1, 0, vec![], tcx.types.bool)
+            }
+            sym::may_contain_pointers => (1, 0, vec![], tcx.types.bool),
+            sym::gc_keep_alive => {
+                (0, 0, vec![Ty::new_imm_ptr(tcx, tcx.types.u8)], Ty::new_unit(tcx))
+            }
             sym::offset => (2, 0, vec![param(0), param(1)], param(0)),
             sym::arith_offset => (
                 1,
@@ -228,6 +228,9 @@ lint_forgetting_references = calls to `std::mem::forget` with a reference instea
     .label = argument has type `{$arg_ty}`
     .note = use `let _ = ...` to ignore the expression or result
 
+lint_gc_in_unscanned_memory = `{$container}` stores a `Gc` in memory from `{$alloc}`, which the collector does not scan
+    .help = use an allocator which implements `GcScannedAllocator`, such as `GcAllocator`
+
 lint_hidden_unicode_codepoints = unicode codepoint changing visible direction of text present in {$label}
     .label = this {$label} contains {$count ->
         [one] an invisible
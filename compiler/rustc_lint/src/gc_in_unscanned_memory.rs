@@ -0,0 +1,107 @@
+use crate::{lints::GcInUnscannedMemoryDiag, LateContext, LateLintPass, LintContext};
+use rustc_hir as hir;
+use rustc_middle::ty::{self, Ty, TypeVisitableExt};
+use rustc_session::{declare_lint, declare_lint_pass};
+use rustc_span::sym;
+use rustc_trait_selection::infer::{InferCtxtExt, TyCtxtInferExt};
+use std::iter;
+
+declare_lint! {
+    /// The `gc_in_unscanned_memory` lint detects containers which can hold a
+    /// `Gc` but allocate their memory from an allocator which the collector
+    /// does not scan.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// #![feature(gc, allocator_api)]
+    /// use std::alloc::System;
+    /// use std::gc::Gc;
+    ///
+    /// let b = Box::new_in(Gc::new(1), System);
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// The collector is conservative: it finds live objects by scanning the
+    /// stack, statics, and memory from allocators implementing
+    /// `GcScannedAllocator`, such as `GcAllocator`. A `Gc` stored in memory
+    /// from any other allocator is invisible to it, so the object it points
+    /// to can be freed while it is still in use.
+    ///
+    /// Statics, including `static mut`s, are scanned, as are `thread_local!`s
+    /// (the standard library registers their values with the collector on
+    /// targets which fall back to OS keys). A container held in one of these
+    /// is still linted if its own memory comes from an unscanned allocator.
+    pub GC_IN_UNSCANNED_MEMORY,
+    Warn,
+    "detects `Gc`s stored in memory which the collector does not scan"
+}
+
+declare_lint_pass!(GcInUnscannedMemory => [GC_IN_UNSCANNED_MEMORY]);
+
+impl<'tcx> LateLintPass<'tcx> for GcInUnscannedMemory {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx hir::Expr<'tcx>) {
+        let ty = cx.typeck_results().expr_ty(expr);
+        // Most crates never use `Gc`, so avoid building an inference context
+        // for every call unless there is something to find.
+        if !contains_gc(cx, ty) {
+            return;
+        }
+        let inputs: Vec<&hir::Expr<'_>> = match expr.kind {
+            hir::ExprKind::Call(_, args) => args.iter().collect(),
+            hir::ExprKind::MethodCall(_, receiver, args, _) => {
+                iter::once(receiver).chain(args).collect()
+            }
+            _ => return,
+        };
+        let Some((container, alloc)) = unscanned_container(cx, ty) else {
+            return;
+        };
+        // Only lint where the container is created, not everywhere it is
+        // passed through.
+        if inputs.iter().any(|input| {
+            cx.typeck_results()
+                .expr_ty(input)
+                .walk()
+                .any(|arg| arg.as_type().is_some_and(|ty| ty == container))
+        }) {
+            return;
+        }
+        cx.emit_span_lint(
+            GC_IN_UNSCANNED_MEMORY,
+            expr.span,
+            GcInUnscannedMemoryDiag { container, alloc },
+        );
+    }
+}
+
+/// Finds a type within `ty` which is parameterised by both an allocator that
+/// is not `GcScannedAllocator` and a type containing a `Gc`. Returns that type
+/// and its allocator.
+fn unscanned_container<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>) -> Option<(Ty<'tcx>, Ty<'tcx>)> {
+    let allocator = cx.tcx.get_diagnostic_item(sym::Allocator)?;
+    let scanned = cx.tcx.lang_items().gc_scanned_allocator_trait()?;
+    let infcx = cx.tcx.infer_ctxt().build();
+    ty.walk().filter_map(|arg| arg.as_type()).find_map(|container| {
+        let ty::Adt(_, args) = container.kind() else {
+            return None;
+        };
+        let alloc = args.types().find(|&arg| {
+            !arg.has_param()
+                && infcx
+                    .type_implements_trait(allocator, [arg], cx.param_env)
+                    .must_apply_modulo_regions()
+                && !infcx
+                    .type_implements_trait(scanned, [arg], cx.param_env)
+                    .must_apply_modulo_regions()
+        })?;
+        args.types().any(|arg| arg != alloc && contains_gc(cx, arg)).then_some((container, alloc))
+    })
+}
+
+fn contains_gc<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>) -> bool {
+    ty.walk().any(|arg| arg.as_type().is_some_and(|ty| ty.is_gc(cx.tcx)))
+}
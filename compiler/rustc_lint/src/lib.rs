@@ -59,6 +59,7 @@
 mod expect;
 mod for_loops_over_fallibles;
 mod foreign_modules;
+mod gc_in_unscanned_memory;
 pub mod hidden_unicode_codepoints;
 mod internal;
 mod invalid_from_utf8;
@@ -98,6 +99,7 @@
 use drop_forget_useless::*;
 use enum_intrinsics_non_enums::EnumIntrinsicsNonEnums;
 use for_loops_over_fallibles::*;
+use gc_in_unscanned_memory::*;
 use hidden_unicode_codepoints::*;
 use internal::*;
 use invalid_from_utf8::*;
@@ -233,6 +235,7 @@ fn lint_mod(tcx: TyCtxt<'_>, module_def_id: LocalModDefId) {
             MissingDoc: MissingDoc,
             AsyncFnInTrait: AsyncFnInTrait,
             NonLocalDefinitions: NonLocalDefinitions::default(),
+            GcInUnscannedMemory: GcInUnscannedMemory,
         ]
     ]
 );
@@ -608,6 +608,15 @@ pub enum PtrNullChecksDiag<'a> {
     FnRet { fn_name: Ident },
 }
 
+// gc_in_unscanned_memory.rs
+#[derive(LintDiagnostic)]
+#[diag(lint_gc_in_unscanned_memory)]
+#[help]
+pub struct GcInUnscannedMemoryDiag<'a> {
+    pub container: Ty<'a>,
+    pub alloc: Ty<'a>,
+}
+
 // for_loops_over_fallibles.rs
 #[derive(LintDiagnostic)]
 #[diag(lint_for_loops_over_fallibles)]
@@ -47,6 +47,7 @@
         FORBIDDEN_LINT_GROUPS,
         FUNCTION_ITEM_REFERENCES,
         FUZZY_PROVENANCE_CASTS,
+        GC_WITHOUT_GC_ALLOCATOR,
         HIDDEN_GLOB_REEXPORTS,
         ILL_FORMED_ATTRIBUTE_INPUT,
         INCOMPLETE_INCLUDE,
@@ -4741,3 +4742,44 @@
     };
     crate_level_only
 }
+
+declare_lint! {
+    /// The `gc_without_gc_allocator` lint detects programs which use
+    /// `std::gc::Gc` without making `std::gc::GcAllocator` the global
+    /// allocator.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore (needs the allocator shim, which is not built with `-C prefer-dynamic`)
+    /// #![feature(gc)]
+    /// use std::gc::Gc;
+    ///
+    /// fn main() {
+    ///     let v = vec![Gc::new(1)];
+    /// }
+    /// ```
+    ///
+    /// This will produce:
+    ///
+    /// ```text
+    /// warning: `Gc` is used without `GcAllocator` as the global allocator
+    ///   |
+    ///   = note: `Gc` is used by `lint_example`
+    ///   = help: add `#[global_allocator] static A: std::gc::GcAllocator = std::gc::GcAllocator;`
+    ///   = note: `#[warn(gc_without_gc_allocator)]` on by default
+    /// ```
+    ///
+    /// ### Explanation
+    ///
+    /// The collector only scans memory which it allocated. A `Gc` stored in
+    /// memory from any other allocator, such as the `Vec` above when the
+    /// global allocator is `System`, is invisible to it, and the object it
+    /// points to may be freed while it is still in use.
+    ///
+    /// This is checked by the crate which generates the allocator shim, so
+    /// it does not fire when the standard library is linked dynamically.
+    pub GC_WITHOUT_GC_ALLOCATOR,
+    Warn,
+    "detects `Gc` being used without `GcAllocator` as the global allocator",
+    crate_level_only
+}
@@ -295,12 +295,17 @@ fn into_args(self) -> (DefId, SimplifiedType) {
     is_mir_available => { cdata.is_item_mir_available(def_id.index) }
     is_ctfe_mir_available => { cdata.is_ctfe_mir_available(def_id.index) }
     cross_crate_inlinable => { cdata.cross_crate_inlinable(def_id.index) }
+    drop_impl_only_deallocates => {
+        cdata.root.tables.drop_impl_only_deallocates.get(cdata, def_id.index)
+    }
 
     dylib_dependency_formats => { cdata.get_dylib_dependency_formats(tcx) }
     is_private_dep => { cdata.private_dep }
     is_panic_runtime => { cdata.root.panic_runtime }
     is_compiler_builtins => { cdata.root.compiler_builtins }
     has_global_allocator => { cdata.root.has_global_allocator }
+    global_allocator_is_gc => { cdata.root.global_allocator_is_gc }
+    crate_uses_gc => { cdata.root.crate_uses_gc }
     has_alloc_error_handler => { cdata.root.has_alloc_error_handler }
     has_panic_handler => { cdata.root.has_panic_handler }
     is_profiler_runtime => { cdata.root.profiler_runtime }
@@ -705,6 +705,8 @@ macro_rules! stat {
                 panic_in_drop_strategy: tcx.sess.opts.unstable_opts.panic_in_drop,
                 edition: tcx.sess.edition(),
                 has_global_allocator: tcx.has_global_allocator(LOCAL_CRATE),
+                global_allocator_is_gc: tcx.global_allocator_is_gc(LOCAL_CRATE),
+                crate_uses_gc: tcx.crate_uses_gc(LOCAL_CRATE),
                 has_alloc_error_handler: tcx.has_alloc_error_handler(LOCAL_CRATE),
                 has_panic_handler: tcx.has_panic_handler(LOCAL_CRATE),
                 has_default_lib_allocator: attr::contains_name(attrs, sym::default_lib_allocator),
@@ -2021,6 +2023,12 @@ fn encode_impls(&mut self) -> LazyArray<TraitImpls> {
                     let coerce_unsized_info = tcx.coerce_unsized_info(def_id).unwrap();
                     record!(self.tables.coerce_unsized_info[def_id] <- coerce_unsized_info);
                 }
+
+                if Some(trait_ref.def_id) == tcx.lang_items().drop_trait() {
+                    self.tables
+                        .drop_impl_only_deallocates
+                        .set(def_id.index, tcx.drop_impl_only_deallocates(def_id));
+                }
             }
         }
 
@@ -500,6 +500,12 @@
         desc { |tcx| "building MIR for `{}`", tcx.def_path_str(key) }
     }
 
+    /// Whether calling this instance may register a finalizer for the value in
+    /// the `Gc` it returns, so that finalizer safety analysis must check it.
+    query registers_finalizer(key: ty::ParamEnvAnd<'tcx, ty::Instance<'tcx>>) -> bool {
+        desc { |tcx| "checking if `{}` registers a finalizer", key.value }
+    }
+
     /// Try to build an abstract representation of the given constant.
     query thir_abstract_const(
         key: DefId
@@ -1348,6 +1354,10 @@
     query drop_method_finalizer_elidable_raw(env: ty::ParamEnvAnd<'tcx, Ty<'tcx>>) -> bool {
         desc { "computing whether `{}` contains types which might need finalizing", env.value }
     }
+    /// Query backing `Ty::finalizer_elidable_with_gc_allocator`.
+    query finalizer_elidable_with_gc_allocator_raw(env: ty::ParamEnvAnd<'tcx, Ty<'tcx>>) -> bool {
+        desc { "computing whether `{}` is `FinalizerElidableWithGcAllocator`", env.value }
+    }
     /// Query backing `Ty::is_finalizer_safe`.
     query is_finalizer_safe_raw(env: ty::ParamEnvAnd<'tcx, Ty<'tcx>>) -> bool {
         desc { "computing whether `{}` is `FinalizerSafe`", env.value }
@@ -1368,6 +1378,22 @@
     query needs_finalizer_raw(env: ty::ParamEnvAnd<'tcx, Ty<'tcx>>) -> bool {
         desc { "computing whether `{}` needs finalizer", env.value }
     }
+    /// Query backing `Ty::needs_finalizer_with_gc_allocator`.
+    query needs_finalizer_with_gc_allocator_raw(env: ty::ParamEnvAnd<'tcx, Ty<'tcx>>) -> bool {
+        desc { "computing whether `{}` needs finalizer with `GcAllocator`", env.value }
+    }
+    /// Query backing `Ty::drop_method_only_deallocates`.
+    query drop_method_only_deallocates_raw(env: ty::ParamEnvAnd<'tcx, Ty<'tcx>>) -> bool {
+        desc { "computing whether the drop method of `{}` only deallocates memory", env.value }
+    }
+    /// Whether the drop method of a `Drop` impl only drops the values it owns
+    /// and deallocates memory. This is computed once, from the built MIR, in
+    /// the crate defining the impl, and read from its metadata elsewhere.
+    query drop_impl_only_deallocates(impl_did: DefId) -> bool {
+        desc { |tcx| "checking whether the drop method in `{}` only deallocates memory", tcx.def_path_str(impl_did) }
+        cache_on_disk_if { impl_did.is_local() }
+        separate_provide_extern
+    }
     /// Query backing `Ty::has_significant_drop_raw`.
     query has_significant_drop_raw(env: ty::ParamEnvAnd<'tcx, Ty<'tcx>>) -> bool {
         desc { "computing whether `{}` has a significant drop", env.value }
@@ -1467,6 +1493,16 @@
         desc { "checking if the crate has_global_allocator" }
         separate_provide_extern
     }
+    /// Whether the crate's `#[global_allocator]` static is a `std::gc::GcAllocator`.
+    query global_allocator_is_gc(_: CrateNum) -> bool {
+        desc { "checking if the crate's global allocator is `GcAllocator`" }
+        separate_provide_extern
+    }
+    /// Whether any body in the crate mentions `std::gc::Gc`.
+    query crate_uses_gc(_: CrateNum) -> bool {
+        desc { "checking if the crate uses `Gc`" }
+        separate_provide_extern
+    }
     query has_alloc_error_handler(_: CrateNum) -> bool {
         // This query depends on untracked global state in CStore
         eval_always
@@ -15,7 +15,7 @@
 use rustc_errors::ErrorGuaranteed;
 use rustc_hir as hir;
 use rustc_hir::def::{CtorOf, DefKind, Res};
-use rustc_hir::def_id::{CrateNum, DefId, LocalDefId};
+use rustc_hir::def_id::{CrateNum, DefId, LocalDefId, LOCAL_CRATE};
 use rustc_index::bit_set::GrowableBitSet;
 use rustc_macros::HashStable;
 use rustc_session::Limit;
@@ -848,6 +848,15 @@ pub fn is_user_visible_dep(self, key: CrateNum) -> bool {
             || self.extern_crate(key.as_def_id()).is_some_and(|e| e.is_direct())
     }
 
+    /// Returns `true` if the `#[global_allocator]` selected for the crate graph
+    /// is `std::gc::GcAllocator`.
+    pub fn gc_is_global_allocator(self) -> bool {
+        iter::once(LOCAL_CRATE)
+            .chain(self.crates(()).iter().copied())
+            .find(|&cnum| self.has_global_allocator(cnum))
+            .is_some_and(|cnum| self.global_allocator_is_gc(cnum))
+    }
+
     /// Whether the item has a host effect param. This is different from `TyCtxt::is_const`,
     /// because the item must also be "maybe const", and the crate where the item is
     /// defined must also have the effects feature enabled.
@@ -1240,6 +1249,15 @@ pub fn is_finalize_unchecked(self, tcx: TyCtxt<'tcx>) -> bool {
         return false;
     }
 
+    pub fn is_non_finalizable(self, tcx: TyCtxt<'tcx>) -> bool {
+        if let ty::Adt(adt_def, ..) = self.kind() {
+            return tcx
+                .get_diagnostic_item(sym::non_finalizable)
+                .map_or(false, |t| adt_def.did() == t);
+        }
+        false
+    }
+
     /// Checks whether values of this type `T` implement the `Freeze`
     /// trait -- frozen types are those that do not contain an
     /// `UnsafeCell` anywhere. This is a language concept used to
@@ -1259,6 +1277,14 @@ pub fn drop_method_finalizer_elidable(
         tcx.drop_method_finalizer_elidable_raw(param_env.and(self))
     }
 
+    pub fn finalizer_elidable_with_gc_allocator(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ty::ParamEnv<'tcx>,
+    ) -> bool {
+        tcx.finalizer_elidable_with_gc_allocator_raw(param_env.and(self))
+    }
+
     pub fn is_finalizer_safe(self, tcx: TyCtxt<'tcx>, param_env: ty::ParamEnv<'tcx>) -> bool {
         tcx.is_finalizer_safe_raw(param_env.and(self))
     }
@@ -1422,7 +1448,9 @@ pub fn has_significant_drop(self, tcx: TyCtxt<'tcx>, param_env: ty::ParamEnv<'tc
     /// If `ty.needs_finalizer(...)` returns `true`, then `ty` is definitely
     /// non-copy and *might* have a destructor attached; if it returns
     /// `false`, then `ty` definitely has no destructor (i.e., no drop glue)
-    /// *or* `ty` implements the `DropMethodFinalizerElidable` trait.
+    /// *or* `ty` implements the `DropMethodFinalizerElidable` trait *or*
+    /// every drop method reachable from `ty` was inferred to do nothing but
+    /// deallocate memory (see `Ty::drop_method_only_deallocates`).
     ///
     /// (Note that this implies that if `ty` has a destructor attached,
     /// then `needs_drop` will definitely return `true` for `ty`.)
@@ -1430,6 +1458,30 @@ pub fn has_significant_drop(self, tcx: TyCtxt<'tcx>, param_env: ty::ParamEnv<'tc
     /// Note that this method is used to check eligible types in unions.
     #[inline]
     pub fn needs_finalizer(self, tcx: TyCtxt<'tcx>, param_env: ty::ParamEnv<'tcx>) -> bool {
+        self.needs_finalizer_helper(tcx, param_env, |tcx, query| tcx.needs_finalizer_raw(query))
+    }
+
+    /// Like `needs_finalizer`, but also elides the drop methods of types
+    /// implementing `FinalizerElidableWithGcAllocator`. This is only the
+    /// answer when `GcAllocator` is the global allocator.
+    #[inline]
+    pub fn needs_finalizer_with_gc_allocator(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ty::ParamEnv<'tcx>,
+    ) -> bool {
+        self.needs_finalizer_helper(tcx, param_env, |tcx, query| {
+            tcx.needs_finalizer_with_gc_allocator_raw(query)
+        })
+    }
+
+    #[cfg_attr(feature = "rustc_no_elision", allow(unused_variables))]
+    fn needs_finalizer_helper(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ty::ParamEnv<'tcx>,
+        needs_finalizer_raw: impl FnOnce(TyCtxt<'tcx>, ty::ParamEnvAnd<'tcx, Ty<'tcx>>) -> bool,
+    ) -> bool {
         #[cfg(feature = "rustc_no_elision")]
         return self.needs_drop(tcx, param_env);
 
@@ -1452,12 +1504,59 @@ pub fn needs_finalizer(self, tcx: TyCtxt<'tcx>, param_env: ty::ParamEnv<'tcx>) -
                     // This doesn't depend on regions, so try to minimize distinct
                     // query keys used.
                     let erased = tcx.normalize_erasing_regions(param_env, query_ty);
-                    tcx.needs_finalizer_raw(param_env.and(erased))
+                    needs_finalizer_raw(tcx, param_env.and(erased))
                 }
             }
         }
     }
 
+    /// Returns `true` if a value of this type may hold a pointer, which a
+    /// garbage collector scanning memory holding the value would need to find.
+    ///
+    /// This is conservative: integers at least as wide as a pointer (which may
+    /// hold tagged or NaN-boxed pointers) and unions (which are often used to
+    /// reinterpret memory, e.g. `MaybeUninit<u8>` buffers) are assumed to be
+    /// able to hold pointers, as is any type which is not fully known.
+    pub fn may_contain_pointers(self, tcx: TyCtxt<'tcx>, param_env: ty::ParamEnv<'tcx>) -> bool {
+        let pointer_bits = tcx.data_layout.pointer_size.bits();
+        match *self.kind() {
+            ty::Int(int_ty) => int_ty.bit_width().map_or(true, |bits| bits >= pointer_bits),
+            ty::Uint(uint_ty) => uint_ty.bit_width().map_or(true, |bits| bits >= pointer_bits),
+            ty::Bool
+            | ty::Char
+            | ty::Float(_)
+            | ty::Str
+            | ty::Never
+            | ty::FnDef(..) => false,
+            ty::Array(ty, _) | ty::Slice(ty) => ty.may_contain_pointers(tcx, param_env),
+            ty::Tuple(tys) => tys.iter().any(|ty| ty.may_contain_pointers(tcx, param_env)),
+            ty::Closure(_, args) => args
+                .as_closure()
+                .upvar_tys()
+                .iter()
+                .any(|ty| ty.may_contain_pointers(tcx, param_env)),
+            ty::Adt(def, _) if def.is_union() => true,
+            ty::Adt(def, args) => def.all_fields().any(|field| {
+                tcx.try_normalize_erasing_regions(param_env, field.ty(tcx, args))
+                    .map_or(true, |ty| ty.may_contain_pointers(tcx, param_env))
+            }),
+            _ => true,
+        }
+    }
+
+    /// Returns `true` if the `Drop` impl of this ADT hands memory back to the
+    /// global allocator and does nothing else except drop the values it owns.
+    ///
+    /// Such a drop method can be elided in a finalizer because the collector
+    /// reclaims the memory anyway (see `DropMethodFinalizerElidable`).
+    pub fn drop_method_only_deallocates(
+        self,
+        tcx: TyCtxt<'tcx>,
+        param_env: ty::ParamEnv<'tcx>,
+    ) -> bool {
+        tcx.drop_method_only_deallocates_raw(param_env.and(self))
+    }
+
     /// Returns `true` if equality for this type is both reflexive and structural.
     ///
     /// Reflexive equality for a type is indicated by an `Eq` impl for that type.
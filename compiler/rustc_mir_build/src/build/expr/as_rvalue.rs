@@ -136,11 +136,12 @@ pub(crate) fn as_rvalue(
                     Rvalue::NullaryOp(NullOp::AlignOf, value_ty),
                 );
 
-                // malloc some memory of suitable size and align:
+                // malloc some memory of suitable size and align. The value's type is passed
+                // along so that the allocator can be told when it holds no pointers.
                 let exchange_malloc = Operand::function_handle(
                     tcx,
                     tcx.require_lang_item(LangItem::ExchangeMalloc, Some(expr_span)),
-                    [],
+                    [value_ty.into()],
                     expr_span,
                 );
                 let storage = this.temp(Ty::new_mut_ptr(tcx, tcx.types.u8), expr_span);
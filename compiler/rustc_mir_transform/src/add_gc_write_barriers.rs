@@ -0,0 +1,335 @@
+//! Inserts a write barrier after every store which may put a pointer into
+//! memory.
+//!
+//! BDWGC can only collect incrementally if it knows which pages have been
+//! written to since it last scanned them. Without help it finds out by
+//! write-protecting the heap and catching the faults, which is slow and clashes
+//! with anything else that uses `mprotect` or signals. With `MANUAL_VDB`, the
+//! program reports its writes itself instead.
+//!
+//! Stores to locals need no barrier, as the stack is rescanned in full at the
+//! end of every collection. Everything else goes through a pointer: a place
+//! with a `Deref` projection (which covers `Cell`, `RefCell`, `Mutex` and
+//! `ptr::write`, the last of which `LowerIntrinsics` has already turned into an
+//! assignment), a call whose return place is behind a pointer, or an intrinsic
+//! which writes through one of its arguments (`copy_nonoverlapping`, `copy`,
+//! the atomics and volatile stores). Each of these is followed by a call to the
+//! `gc_write_barrier` lang item, which does nothing unless the type stored may
+//! contain a pointer.
+//!
+//! The pass runs before inlining, so that code inlined from other crates
+//! brings its barriers along rather than getting a second set. Inline assembly
+//! is not instrumented.
+
+use rustc_hir as hir;
+use rustc_hir::def_id::DefId;
+use rustc_middle::mir::interpret::Scalar;
+use rustc_middle::mir::*;
+use rustc_middle::ty::{self, ParamEnv, Ty, TyCtxt};
+use rustc_session::Session;
+use rustc_span::source_map::Spanned;
+use rustc_span::sym;
+
+pub struct AddGcWriteBarriers;
+
+impl<'tcx> MirPass<'tcx> for AddGcWriteBarriers {
+    fn is_enabled(&self, _: &Session) -> bool {
+        cfg!(feature = "rustc_gc_write_barriers")
+    }
+
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        let Some(barrier) = tcx.lang_items().gc_write_barrier_fn() else {
+            return;
+        };
+        let def_id = body.source.def_id();
+        if def_id == barrier || body.source.promoted.is_some() {
+            return;
+        }
+        // Consts and statics are only ever evaluated at compile time.
+        if let Some(local) = def_id.as_local()
+            && matches!(
+                tcx.hir().body_const_context(local),
+                Some(hir::ConstContext::Const { .. } | hir::ConstContext::Static(_))
+            )
+        {
+            return;
+        }
+        trace!("Running AddGcWriteBarriers on {:?}", body.source);
+
+        let param_env = tcx.param_env_reveal_all_normalized(def_id);
+        let mut inserter = Inserter { tcx, param_env, barrier, body };
+        // Cleanup blocks are instrumented too: an assignment whose drop of the
+        // old value unwinds still stores the new value on the cleanup path.
+        // The barrier never unwinds, so calling it there is fine.
+        for bb in inserter.body.basic_blocks.indices() {
+            inserter.instrument_terminator(bb);
+            // Backwards, so that splitting the block leaves the statements
+            // still to be visited where they were.
+            for statement_index in (0..inserter.body.basic_blocks[bb].statements.len()).rev() {
+                inserter.instrument_statement(Location { block: bb, statement_index });
+            }
+        }
+    }
+}
+
+/// A write the barrier is told about: `count` values of type `ty` were stored
+/// at the address held by `dst`.
+struct Write<'tcx> {
+    ty: Ty<'tcx>,
+    dst: Local,
+    count: Operand<'tcx>,
+}
+
+struct Inserter<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    param_env: ParamEnv<'tcx>,
+    barrier: DefId,
+    body: &'a mut Body<'tcx>,
+}
+
+impl<'a, 'tcx> Inserter<'a, 'tcx> {
+    /// Whether a store of a `ty` needs a barrier. Generic types are decided
+    /// by the barrier itself once they are monomorphized.
+    fn needs_barrier(&self, ty: Ty<'tcx>) -> bool {
+        ty.may_contain_pointers(self.tcx, self.param_env)
+    }
+
+    fn new_temp(&mut self, ty: Ty<'tcx>, source_info: SourceInfo) -> Local {
+        self.body.local_decls.push(LocalDecl::with_source_info(ty, source_info))
+    }
+
+    /// Returns a statement which saves the address of `place` in a new local.
+    fn address_of(
+        &mut self,
+        place: Place<'tcx>,
+        source_info: SourceInfo,
+    ) -> (Statement<'tcx>, Write<'tcx>) {
+        let ty = place.ty(&*self.body, self.tcx).ty;
+        let dst = self.new_temp(Ty::new_imm_ptr(self.tcx, ty), source_info);
+        let statement = Statement {
+            source_info,
+            kind: StatementKind::Assign(Box::new((
+                dst.into(),
+                Rvalue::AddressOf(Mutability::Not, place),
+            ))),
+        };
+        (statement, Write { ty, dst, count: self.one(source_info) })
+    }
+
+    fn one(&self, source_info: SourceInfo) -> Operand<'tcx> {
+        Operand::const_from_scalar(
+            self.tcx,
+            self.tcx.types.usize,
+            Scalar::from_target_usize(1, &self.tcx),
+            source_info.span,
+        )
+    }
+
+    /// Returns a statement which saves the value of the pointer `operand` (of
+    /// type `*mut T` or `*const T`) in a new local, so that it is still
+    /// available after the write which consumes `operand`.
+    fn save_pointer(
+        &mut self,
+        operand: &Operand<'tcx>,
+        count: Operand<'tcx>,
+        source_info: SourceInfo,
+    ) -> (Statement<'tcx>, Write<'tcx>) {
+        let ty = operand.ty(&*self.body, self.tcx).builtin_deref(true).unwrap().ty;
+        let ptr_ty = Ty::new_imm_ptr(self.tcx, ty);
+        let dst = self.new_temp(ptr_ty, source_info);
+        let statement = Statement {
+            source_info,
+            kind: StatementKind::Assign(Box::new((
+                dst.into(),
+                Rvalue::Cast(CastKind::PtrToPtr, copy_of(operand), ptr_ty),
+            ))),
+        };
+        (statement, Write { ty, dst, count })
+    }
+
+    /// Returns a statement which saves `count` in a new local, if it isn't a
+    /// constant.
+    fn save_count(
+        &mut self,
+        count: &Operand<'tcx>,
+        source_info: SourceInfo,
+    ) -> (Option<Statement<'tcx>>, Operand<'tcx>) {
+        if let Operand::Constant(_) = count {
+            return (None, count.clone());
+        }
+        let saved = self.new_temp(self.tcx.types.usize, source_info);
+        let statement = Statement {
+            source_info,
+            kind: StatementKind::Assign(Box::new((saved.into(), Rvalue::Use(copy_of(count))))),
+        };
+        (Some(statement), Operand::Copy(saved.into()))
+    }
+
+    fn instrument_statement(&mut self, location: Location) {
+        let statement =
+            &self.body.basic_blocks[location.block].statements[location.statement_index];
+        let source_info = statement.source_info;
+        let (before, after, write) = match &statement.kind {
+            StatementKind::Assign(box (place, _)) if place.is_indirect() => {
+                let place = *place;
+                if !self.needs_barrier(place.ty(&*self.body, self.tcx).ty) {
+                    return;
+                }
+                let (save, write) = self.address_of(place, source_info);
+                (vec![], vec![save], write)
+            }
+            StatementKind::Intrinsic(box NonDivergingIntrinsic::CopyNonOverlapping(
+                CopyNonOverlapping { dst, count, .. },
+            )) => {
+                let (dst, count) = (dst.clone(), count.clone());
+                let ty = dst.ty(&*self.body, self.tcx).builtin_deref(true).unwrap().ty;
+                if !self.needs_barrier(ty) {
+                    return;
+                }
+                let (save_count, count) = self.save_count(&count, source_info);
+                let (save_dst, write) = self.save_pointer(&dst, count, source_info);
+                (save_count.into_iter().chain([save_dst]).collect(), vec![], write)
+            }
+            _ => return,
+        };
+
+        let block_data = &mut self.body.basic_blocks.as_mut()[location.block];
+        let rest = block_data.statements.split_off(location.statement_index + 1);
+        let terminator = block_data.terminator.take();
+        let is_cleanup = block_data.is_cleanup;
+        let stored = block_data.statements.pop().unwrap();
+        block_data.statements.extend(before);
+        block_data.statements.push(stored);
+        block_data.statements.extend(after);
+        let rest = self.body.basic_blocks.as_mut().push(BasicBlockData {
+            statements: rest,
+            terminator,
+            is_cleanup,
+        });
+        let call = self.call_barrier(write, source_info, rest);
+        self.body.basic_blocks.as_mut()[location.block].terminator = Some(call);
+    }
+
+    fn instrument_terminator(&mut self, bb: BasicBlock) {
+        let terminator = self.body.basic_blocks[bb].terminator();
+        let source_info = terminator.source_info;
+        let TerminatorKind::Call { func, args, destination, target: Some(target), .. } =
+            &terminator.kind
+        else {
+            return;
+        };
+        let (func, args, destination, target) = (func.clone(), args.clone(), *destination, *target);
+
+        let mut saves = Vec::new();
+        let mut writes = Vec::new();
+        if let ty::FnDef(def_id, generic_args) = *func.ty(&*self.body, self.tcx).kind()
+            && let Some(intrinsic) = self.tcx.intrinsic(def_id)
+        {
+            let name = intrinsic.name;
+            let dsts: &[usize] = match name {
+                sym::copy => &[1],
+                sym::volatile_copy_memory
+                | sym::volatile_copy_nonoverlapping_memory
+                | sym::volatile_store
+                | sym::unaligned_volatile_store => &[0],
+                sym::typed_swap => &[0, 1],
+                _ if name.as_str().starts_with("atomic_")
+                    && !name.as_str().starts_with("atomic_load")
+                    && !name.as_str().contains("fence") =>
+                {
+                    &[0]
+                }
+                _ => &[],
+            };
+            if !dsts.is_empty() && self.needs_barrier(generic_args.type_at(0)) {
+                let count = match name {
+                    sym::copy
+                    | sym::volatile_copy_memory
+                    | sym::volatile_copy_nonoverlapping_memory => {
+                        let (save, count) = self.save_count(&args[2].node, source_info);
+                        saves.extend(save);
+                        count
+                    }
+                    _ => self.one(source_info),
+                };
+                for &i in dsts {
+                    let (save, write) =
+                        self.save_pointer(&args[i].node, count.clone(), source_info);
+                    saves.push(save);
+                    writes.push(write);
+                }
+            }
+        }
+
+        let mut after = Vec::new();
+        if destination.is_indirect() && self.needs_barrier(destination.ty(&*self.body, self.tcx).ty)
+        {
+            let (save, write) = self.address_of(destination, source_info);
+            after.push(save);
+            writes.push(write);
+        }
+        if writes.is_empty() {
+            return;
+        }
+
+        // The pointers are saved before the call, which may move them, and
+        // the barriers are called on the way to `target`.
+        let is_cleanup = self.body.basic_blocks[bb].is_cleanup;
+        let mut next = target;
+        for write in writes.into_iter().rev() {
+            let call = self.call_barrier(write, source_info, next);
+            next = self.body.basic_blocks.as_mut().push(BasicBlockData {
+                statements: vec![],
+                terminator: Some(call),
+                is_cleanup,
+            });
+        }
+        self.body.basic_blocks.as_mut()[next].statements = after;
+        let block_data = &mut self.body.basic_blocks.as_mut()[bb];
+        block_data.statements.extend(saves);
+        let TerminatorKind::Call { target, .. } = &mut block_data.terminator_mut().kind else {
+            bug!()
+        };
+        *target = Some(next);
+    }
+
+    /// Builds a call to the barrier for `write`, which continues to `target`.
+    fn call_barrier(
+        &mut self,
+        write: Write<'tcx>,
+        source_info: SourceInfo,
+        target: BasicBlock,
+    ) -> Terminator<'tcx> {
+        let unit = self.new_temp(self.tcx.types.unit, source_info);
+        Terminator {
+            source_info,
+            kind: TerminatorKind::Call {
+                func: Operand::function_handle(
+                    self.tcx,
+                    self.barrier,
+                    [write.ty.into()],
+                    source_info.span,
+                ),
+                args: vec![
+                    Spanned { node: Operand::Move(write.dst.into()), span: source_info.span },
+                    Spanned { node: write.count, span: source_info.span },
+                ],
+                destination: unit.into(),
+                target: Some(target),
+                // The barrier is `#[rustc_nounwind]`.
+                unwind: UnwindAction::Unreachable,
+                call_source: CallSource::Misc,
+                fn_span: source_info.span,
+            },
+        }
+    }
+}
+
+/// Reads `operand` without moving out of it. Only used for pointers and
+/// `usize`s, which are `Copy`.
+fn copy_of<'tcx>(operand: &Operand<'tcx>) -> Operand<'tcx> {
+    match operand {
+        Operand::Move(place) => Operand::Copy(*place),
+        _ => operand.clone(),
+    }
+}
@@ -1,15 +1,21 @@
 #![allow(rustc::untranslatable_diagnostic)]
 #![allow(rustc::diagnostic_outside_of_impl)]
-use rustc_data_structures::fx::FxHashSet;
+use rustc_data_structures::fx::{FxHashSet, FxIndexSet};
 use rustc_hir::def_id::DefId;
 use rustc_hir::lang_items::LangItem;
+use rustc_index::bit_set::BitSet;
+use rustc_index::IndexVec;
+use rustc_middle::mir::visit::MutatingUseContext;
 use rustc_middle::mir::visit::PlaceContext;
 use rustc_middle::mir::visit::TyContext;
 use rustc_middle::mir::visit::Visitor;
 use rustc_middle::mir::*;
+use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::{self, ParamEnv, Ty, TyCtxt};
+use rustc_session::code_stats::{FinalizerElision, FsaOutcome, GcReportInfo};
 use rustc_span::symbol::sym;
 use rustc_span::Span;
+use std::cell::{Cell, RefCell};
 use std::collections::VecDeque;
 
 use bitflags::bitflags;
@@ -75,7 +81,9 @@ fn new(span: Span, drop_ty: Ty<'tcx>) -> Self {
 
 impl<'tcx> MirPass<'tcx> for CheckFinalizers {
     fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
-        if tcx.sess.opts.cg.no_finalizer_safety_analysis {
+        if tcx.sess.opts.cg.no_finalizer_safety_analysis
+            && !tcx.sess.opts.unstable_opts.print_gc_report
+        {
             return;
         }
         trace!("Running FSA on {:?}", body.source);
@@ -106,6 +114,13 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
             };
 
             let ret_ty = fn_ty.fn_sig(tcx).output().skip_binder();
+            // `Gc::pin` returns its `Gc` wrapped in a `Pin`.
+            let ret_ty = match *ret_ty.kind() {
+                ty::Adt(adt_def, args) if Some(adt_def.did()) == tcx.lang_items().pin_type() => {
+                    args.type_at(0)
+                }
+                _ => ret_ty,
+            };
 
             // The following is a gross hack for performance reasons!
             //
@@ -124,26 +139,35 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
             //      b) the generic substitution for the return type (which is readily available) is
             //      not a `Gc<T>`. In practice, this means we only actually end up having to
             //      resolve fn calls to their precise instance when they actually are some kind
-            //      of `Gc` constructor (we still check for the attribute later on to make sure
-            //      though!).
-            if !in_std_lib(tcx, *fn_did)
-                || !ret_ty.is_gc(tcx)
-                || ty::Instance::expect_resolve(tcx, param_env, *fn_did, substs)
-                    .def
-                    .get_attrs(tcx, sym::rustc_fsa_entry_point)
-                    .next()
-                    .is_none()
-            {
+            //      of `Gc` constructor (we still check that it registers a finalizer later on to
+            //      make sure though!).
+            if !in_std_lib(tcx, *fn_did) || !ret_ty.is_gc(tcx) {
+                continue;
+            }
+            let instance = ty::Instance::expect_resolve(tcx, param_env, *fn_did, substs);
+            if !tcx.registers_finalizer(param_env.and(instance)) {
                 continue;
             }
+            // Values registered with a `Guardian` are dropped by the thread which polls it rather
+            // than by the finalizer thread, so their drop methods may be `!Send` and may use
+            // thread-locals.
+            let checks = if tcx.is_diagnostic_item(sym::gc_guardian_register, instance.def_id()) {
+                FSAChecks::all() - FSAChecks::SEND_SYNC - FSAChecks::THREAD_LOCALS
+            } else {
+                FSAChecks::all()
+            };
+            // Some constructors, such as `Default::default`, take no arguments. In that case the
+            // best we can do is point at the call itself.
+            let arg_span = args.first().map_or(source_info.span, |arg| arg.span);
             FSAEntryPointCtxt::new(
                 source_info.span,
-                args[0].span,
+                arg_span,
                 ret_ty.gced_ty(tcx),
+                checks,
                 tcx,
                 param_env,
             )
-            .check_drop_glue();
+            .check();
         }
     }
 }
@@ -157,8 +181,14 @@ struct FSAEntryPointCtxt<'tcx> {
     arg_span: Span,
     /// Type of the GC'd value created by the entry point.
     value_ty: Ty<'tcx>,
+    /// The checks which apply to every drop method reached from this entry point.
+    checks: FSAChecks,
     tcx: TyCtxt<'tcx>,
     param_env: ParamEnv<'tcx>,
+    /// The number of FSA errors emitted for this entry point.
+    error_count: Cell<usize>,
+    /// Every drop method checked for this entry point. Only used for `-Z print-gc-report`.
+    drop_methods: RefCell<FxIndexSet<ty::Instance<'tcx>>>,
 }
 
 impl<'tcx> FSAEntryPointCtxt<'tcx> {
@@ -166,19 +196,128 @@ fn new(
         fn_span: Span,
         arg_span: Span,
         value_ty: Ty<'tcx>,
+        checks: FSAChecks,
         tcx: TyCtxt<'tcx>,
         param_env: ParamEnv<'tcx>,
     ) -> Self {
-        Self { fn_span, arg_span, value_ty, tcx, param_env }
+        Self {
+            fn_span,
+            arg_span,
+            value_ty,
+            checks,
+            tcx,
+            param_env,
+            error_count: Cell::new(0),
+            drop_methods: RefCell::new(FxIndexSet::default()),
+        }
     }
 
-    fn check_drop_glue(&self) {
-        if !self.value_ty.needs_finalizer(self.tcx, self.param_env)
-            || self.value_ty.is_finalize_unchecked(self.tcx)
-        {
-            return;
+    fn check(&self) {
+        // `Gc::new` also skips the finalizers of `FinalizerElidableWithGcAllocator` types
+        // when it finds `GcAllocator` is the global allocator, which is certain if this
+        // crate can already see it.
+        let needs_finalizer = self.value_ty.needs_finalizer(self.tcx, self.param_env);
+        let elided_with_gc_allocator = needs_finalizer
+            && self.tcx.gc_is_global_allocator()
+            && !self.value_ty.needs_finalizer_with_gc_allocator(self.tcx, self.param_env);
+        let needs_finalizer = needs_finalizer && !elided_with_gc_allocator;
+        let outcome = if self.tcx.sess.opts.cg.no_finalizer_safety_analysis {
+            FsaOutcome::Disabled
+        } else if !needs_finalizer {
+            FsaOutcome::NoFinalizer
+        } else if self.value_ty.is_finalize_unchecked(self.tcx) {
+            FsaOutcome::FinalizeUnchecked
+        } else {
+            self.check_drop_glue()
+        };
+
+        if self.tcx.sess.opts.unstable_opts.print_gc_report {
+            self.record_report(needs_finalizer, elided_with_gc_allocator, outcome);
         }
+    }
+
+    fn record_report(
+        &self,
+        needs_finalizer: bool,
+        elided_with_gc_allocator: bool,
+        fsa: FsaOutcome,
+    ) {
+        let elision = if needs_finalizer {
+            None
+        } else if !self.value_ty.needs_drop(self.tcx, self.param_env) {
+            Some(FinalizerElision::NoDropGlue)
+        } else if self.value_ty.is_non_finalizable(self.tcx) {
+            Some(FinalizerElision::NonFinalizable)
+        } else if elided_with_gc_allocator {
+            Some(FinalizerElision::FinalizerElidableWithGcAllocator)
+        } else {
+            Some(self.elided_drop_methods_reason())
+        };
+        let drop_methods = self
+            .drop_methods
+            .borrow()
+            .iter()
+            .map(|instance| {
+                with_no_trimmed_paths!(
+                    self.tcx.def_path_str_with_args(instance.def_id(), instance.args)
+                )
+            })
+            .collect();
+        self.tcx.sess.code_stats.record_gc_report(GcReportInfo {
+            gc_ty: with_no_trimmed_paths!(format!("Gc<{}>", self.value_ty)),
+            fn_span: self.fn_span,
+            arg_span: self.arg_span,
+            needs_finalizer,
+            elision,
+            fsa,
+            drop_methods,
+        });
+    }
 
+    /// Finds why the drop methods in a type which `needs_finalizer` elides were
+    /// elided: `DropMethodFinalizerElidable`, unless at least one of them was only
+    /// inferred to deallocate.
+    fn elided_drop_methods_reason(&self) -> FinalizerElision {
+        let mut seen = FxHashSet::default();
+        let mut queue = vec![self.value_ty];
+        while let Some(ty) = queue.pop() {
+            if !seen.insert(ty) || !ty.needs_drop(self.tcx, self.param_env) {
+                continue;
+            }
+            match *ty.kind() {
+                ty::Adt(adt_def, _)
+                    if adt_def.is_manually_drop() || ty.is_non_finalizable(self.tcx) => {}
+                ty::Adt(adt_def, args) => {
+                    if adt_def.has_dtor(self.tcx)
+                        && !ty.drop_method_finalizer_elidable(self.tcx, self.param_env)
+                    {
+                        return FinalizerElision::DropMethodOnlyDeallocates;
+                    }
+                    queue.extend(args.types());
+                    queue.extend(adt_def.all_fields().map(|field| {
+                        let field_ty = field.ty(self.tcx, args);
+                        self.tcx
+                            .try_normalize_erasing_regions(self.param_env, field_ty)
+                            .unwrap_or(field_ty)
+                    }));
+                }
+                ty::Array(elem_ty, _) | ty::Slice(elem_ty) => queue.push(elem_ty),
+                ty::Tuple(tys) => queue.extend(tys),
+                ty::Closure(_, args) => queue.extend(args.as_closure().upvar_tys()),
+                _ => {}
+            }
+        }
+        FinalizerElision::DropMethodFinalizerElidable
+    }
+
+    fn outcome(&self) -> FsaOutcome {
+        match self.error_count.get() {
+            0 => FsaOutcome::Passed,
+            n => FsaOutcome::Failed(n),
+        }
+    }
+
+    fn check_drop_glue(&self) -> FsaOutcome {
         let mut errors = Vec::new();
         let mut tys = vec![self.value_ty];
 
@@ -188,13 +327,13 @@ fn check_drop_glue(&self) {
                 break;
             };
 
-            let mut checks = FSAChecks::all();
+            let mut checks = self.checks;
             if ty.is_finalize_unchecked(self.tcx)
                 || ty.is_copy_modulo_regions(self.tcx, self.param_env)
             {
                 // The user has either explicitly told us not to do FSA on `T` or it implements
                 // `Copy` (i.e. it has no drop glue). We are done.
-                return;
+                return self.outcome();
             }
             if ty.is_send(self.tcx, self.param_env) && ty.is_sync(self.tcx, self.param_env) {
                 // `T` (and thus its fields) are `Send + Sync`. Any projection of `T`'s fields in
@@ -265,6 +404,7 @@ fn check_drop_glue(&self) {
                             poly_drop_fn_did,
                             self.tcx.mk_args_trait(ty, substs.into_iter()),
                         );
+                        self.drop_methods.borrow_mut().insert(drop_instance);
                         match DropCtxt::new(drop_instance, ty, checks, self).check() {
                             Err(_) if in_std_lib(self.tcx, def.did()) => {
                                 let fn_info = FnInfo::new(rustc_span::DUMMY_SP, ty);
@@ -294,6 +434,7 @@ fn check_drop_glue(&self) {
             }
         }
         errors.into_iter().for_each(|e| self.emit_error(e));
+        self.outcome()
     }
 
     /// Attempts to load the monomorphized version of a MIR body for the given instance if it's
@@ -463,6 +604,7 @@ fn emit_error(&self, error_kind: FinalizerErrorKind<'tcx>) {
             format!("caused by trying to construct a `Gc<{}>` here.", self.value_ty),
         );
         err.emit();
+        self.error_count.set(self.error_count.get() + 1);
     }
 }
 
@@ -550,11 +692,14 @@ struct FuncCtxt<'dcx, 'ecx, 'tcx> {
     dcx: &'dcx mut DropCtxt<'ecx, 'tcx>,
     errors: Vec<FinalizerErrorKind<'tcx>>,
     error_locs: FxHashSet<Location>,
+    /// Locals which point into an immutable `Sync` static item. See `static_rooted_locals`.
+    static_roots: BitSet<Local>,
 }
 
 impl<'dcx, 'ecx, 'tcx> FuncCtxt<'dcx, 'ecx, 'tcx> {
     fn new(body: &'dcx Body<'tcx>, dcx: &'dcx mut DropCtxt<'ecx, 'tcx>) -> Self {
-        Self { body, dcx, errors: Vec::new(), error_locs: FxHashSet::default() }
+        let static_roots = static_rooted_locals(body, dcx.ecx.tcx, dcx.ecx.param_env);
+        Self { body, dcx, errors: Vec::new(), error_locs: FxHashSet::default(), static_roots }
     }
 
     fn check(mut self) -> Result<(), Vec<FinalizerErrorKind<'tcx>>> {
@@ -589,6 +734,14 @@ fn visit_projection(&mut self, place_ref: PlaceRef<'tcx>, _: PlaceContext, locat
         if self.dcx.checks.is_empty() {
             return;
         }
+        if self.static_roots.contains(place_ref.local)
+            && derefs_shared_refs_only(self.body, self.tcx(), place_ref)
+        {
+            // Everything reachable through shared references from an immutable `Sync` static
+            // lives for `'static` and can be used from any thread, so it is always safe to use
+            // inside a finalizer.
+            return;
+        }
         // A single projection can be comprised of other 'inner' projections (e.g. self.a.b.c), so
         // this loop ensures that the types of each intermediate projection is extracted and then
         // checked.
@@ -614,6 +767,9 @@ fn visit_projection(&mut self, place_ref: PlaceRef<'tcx>, _: PlaceContext, locat
                 //      2. Unsafe code can and does transmute lifetimes up to 'static then use
                 //         runtime properties to ensure that the reference is valid. FSA would
                 //         not catch this and could allow unsound programs.
+                //
+                // The exception is references which are read out of a `static` item, which are
+                // handled separately (see `static_rooted_locals`).
                 self.push_error(location, FinalizerErrorKind::UnsoundReference(fn_info, proj_info));
                 break;
             }
@@ -683,6 +839,9 @@ fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location
                 ) else {
                     bug!();
                 };
+                if let Some(instance) = instance {
+                    self.ecx().drop_methods.borrow_mut().insert(instance);
+                }
                 let span = terminator.source_info.span;
                 let info = FnInfo::new(span, self.dcx.drop_ty);
                 (instance, info)
@@ -731,6 +890,183 @@ fn visit_ty(&mut self, ty: Ty<'tcx>, cx: TyContext) {
     }
 }
 
+/// Finds the locals in `body` which point into an immutable `static` item whose type is `Sync`.
+///
+/// A local qualifies if it is assigned exactly once, is not mutated in any other way, and that
+/// assignment is one of:
+///
+/// - a constant pointing to such a `static` (i.e. the MIR for a use of the static item);
+/// - a shared reborrow of a place which is rooted in a qualifying local;
+/// - a shared reference which is read out of a place rooted in a qualifying local.
+///
+/// Such a `static` can never be freed or mutated without synchronisation, so unlike other
+/// references, anything reached through these locals is guaranteed to outlive a finalizer even
+/// though its `'static` lifetime has been erased by the time FSA runs.
+fn static_rooted_locals<'tcx>(
+    body: &Body<'tcx>,
+    tcx: TyCtxt<'tcx>,
+    param_env: ParamEnv<'tcx>,
+) -> BitSet<Local> {
+    struct LocalAssignments<'a, 'tcx> {
+        stores: IndexVec<Local, usize>,
+        mutated: BitSet<Local>,
+        rvalues: Vec<(Local, &'a Rvalue<'tcx>)>,
+    }
+
+    impl<'a, 'tcx> LocalAssignments<'a, 'tcx> {
+        fn visit(&mut self, body: &'a Body<'tcx>) {
+            for (bb, data) in body.basic_blocks.iter_enumerated() {
+                for stmt in &data.statements {
+                    if let StatementKind::Assign(box (place, rvalue)) = &stmt.kind
+                        && let Some(local) = place.as_local()
+                    {
+                        self.rvalues.push((local, rvalue));
+                    }
+                }
+                self.visit_basic_block_data(bb, data);
+            }
+        }
+    }
+
+    impl<'a, 'tcx> Visitor<'tcx> for LocalAssignments<'a, 'tcx> {
+        fn visit_local(&mut self, local: Local, context: PlaceContext, _: Location) {
+            match context {
+                PlaceContext::MutatingUse(MutatingUseContext::Store) => self.stores[local] += 1,
+                PlaceContext::MutatingUse(_) => {
+                    self.mutated.insert(local);
+                }
+                _ => (),
+            }
+        }
+    }
+
+    let mut assignments = LocalAssignments {
+        stores: IndexVec::from_elem(0, &body.local_decls),
+        mutated: BitSet::new_empty(body.local_decls.len()),
+        rvalues: Vec::new(),
+    };
+    assignments.visit(body);
+
+    let is_sync_static = |did: DefId| {
+        !tcx.is_mutable_static(did)
+            && tcx.type_of(did).instantiate_identity().is_sync(tcx, param_env)
+    };
+    let is_shared_ref = |ty: Ty<'tcx>| matches!(ty.kind(), ty::Ref(_, _, Mutability::Not));
+
+    let mut roots = BitSet::new_empty(body.local_decls.len());
+    loop {
+        let mut changed = false;
+        for &(local, rvalue) in &assignments.rvalues {
+            if roots.contains(local)
+                || assignments.stores[local] != 1
+                || assignments.mutated.contains(local)
+                || local.as_usize() <= body.arg_count
+            {
+                continue;
+            }
+            let rooted = match rvalue {
+                Rvalue::Use(Operand::Constant(c)) => {
+                    c.check_static_ptr(tcx).is_some_and(|did| is_sync_static(did))
+                }
+                Rvalue::Use(Operand::Copy(place) | Operand::Move(place))
+                | Rvalue::CopyForDeref(place) => {
+                    roots.contains(place.local)
+                        && derefs_shared_refs_only(body, tcx, place.as_ref())
+                        && is_shared_ref(place.ty(body, tcx).ty)
+                }
+                Rvalue::Ref(_, BorrowKind::Shared, place) => {
+                    roots.contains(place.local)
+                        && derefs_shared_refs_only(body, tcx, place.as_ref())
+                }
+                _ => false,
+            };
+            if rooted {
+                roots.insert(local);
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    roots
+}
+
+/// Returns `true` if every dereference in `place_ref` goes through a shared reference.
+fn derefs_shared_refs_only<'tcx>(
+    body: &Body<'tcx>,
+    tcx: TyCtxt<'tcx>,
+    place_ref: PlaceRef<'tcx>,
+) -> bool {
+    place_ref.iter_projections().all(|(base, elem)| {
+        elem != ProjectionElem::Deref
+            || matches!(base.ty(body, tcx).ty.kind(), ty::Ref(_, _, Mutability::Not))
+    })
+}
+
+/// The maximum number of standard library bodies followed when deciding whether a call
+/// registers a finalizer.
+const MAX_CONSTRUCTOR_DEPTH: usize = 32;
+
+/// Returns `true` if calling `instance` may register a finalizer for the value in the `Gc` it
+/// returns.
+///
+/// Rather than relying on every `Gc` constructor being annotated, this follows the standard
+/// library through any callee which itself returns a `Gc`, until it reaches a function marked
+/// `#[rustc_fsa_entry_point]`. This means that constructors which reach `Gc::new` through trait
+/// dispatch, like `Default::default` or `From<[T; N]>`, are checked without needing their own
+/// attribute, while constructors which never go through an entry point, like
+/// `Gc::new_unfinalizable`, are not. The MIR inliner never inlines entry points, so the calls to
+/// them are still visible in the optimized MIR of their callers.
+///
+/// Only bodies from other crates are followed: when compiling the standard library itself, its
+/// optimized MIR is not available yet, and asking for it here would create a query cycle.
+pub(crate) fn registers_finalizer<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    query: ty::ParamEnvAnd<'tcx, ty::Instance<'tcx>>,
+) -> bool {
+    let (param_env, instance) = query.into_parts();
+    let mut visited = FxHashSet::default();
+    let mut queue = vec![instance];
+    while let Some(instance) = queue.pop() {
+        if !visited.insert(instance) || visited.len() > MAX_CONSTRUCTOR_DEPTH {
+            continue;
+        }
+        if instance.def.get_attrs(tcx, sym::rustc_fsa_entry_point).next().is_some() {
+            return true;
+        }
+        let ty::InstanceDef::Item(did) = instance.def else {
+            continue;
+        };
+        if did.is_local() || !in_std_lib(tcx, did) || !tcx.is_mir_available(did) {
+            continue;
+        }
+        let Ok(body) = instance.try_instantiate_mir_and_normalize_erasing_regions(
+            tcx,
+            param_env,
+            ty::EarlyBinder::bind(tcx.instance_mir(instance.def).clone()),
+        ) else {
+            continue;
+        };
+        for bb in body.basic_blocks.iter() {
+            let TerminatorKind::Call { func, .. } = &bb.terminator().kind else {
+                continue;
+            };
+            let fn_ty = func.ty(&body, tcx);
+            let ty::FnDef(fn_did, substs) = *fn_ty.kind() else {
+                continue;
+            };
+            if !fn_ty.fn_sig(tcx).output().skip_binder().is_gc(tcx) {
+                continue;
+            }
+            if let Ok(Some(callee)) = ty::Instance::resolve(tcx, param_env, fn_did, substs) {
+                queue.push(callee);
+            }
+        }
+    }
+    false
+}
+
 fn in_std_lib<'tcx>(tcx: TyCtxt<'tcx>, did: DefId) -> bool {
     let alloc_crate = tcx.get_diagnostic_item(sym::Rc).map_or(false, |x| did.krate == x.krate);
     let core_crate = tcx.get_diagnostic_item(sym::RefCell).map_or(false, |x| did.krate == x.krate);
@@ -426,6 +426,13 @@ fn check_codegen_attributes(
             return Err("#[rustc_no_mir_inline]");
         }
 
+        // Finalizer safety analysis finds `Gc` constructors in other crates by looking for calls
+        // to entry points in their optimized MIR, so those calls have to survive MIR inlining.
+        // LLVM is still free to inline them.
+        if self.tcx.has_attr(callsite.callee.def_id(), sym::rustc_fsa_entry_point) {
+            return Err("#[rustc_fsa_entry_point]");
+        }
+
         if let InlineAttr::Never = callee_attrs.inline {
             return Err("never inline hint");
         }
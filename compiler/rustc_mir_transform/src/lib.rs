@@ -49,6 +49,7 @@
 
 mod abort_unwinding_calls;
 mod add_call_guards;
+mod add_gc_write_barriers;
 mod add_moves_for_packed_drops;
 mod add_retag;
 mod check_const_item_mutation;
@@ -92,6 +93,7 @@
 mod multiple_return_terminators;
 mod normalize_array_len;
 mod nrvo;
+mod place_gc_barriers;
 mod prettify;
 mod promote_consts;
 mod ref_prop;
@@ -128,6 +130,7 @@ pub fn provide(providers: &mut Providers) {
     providers.queries = query::Providers {
         mir_keys,
         mir_built,
+        registers_finalizer: check_finalizers::registers_finalizer,
         mir_const_qualif,
         mir_promoted,
         mir_drops_elaborated_and_const_checked,
@@ -330,6 +333,14 @@ fn mir_promoted(
     };
     // has_ffi_unwind_calls query uses the raw mir, so make sure it is run.
     tcx.ensure_with_value().has_ffi_unwind_calls(def);
+    // Finalizer elision judges drop methods on their built MIR.
+    if let Some(impl_did) = tcx.impl_of_method(def.to_def_id())
+        && tcx
+            .trait_id_of_impl(impl_did)
+            .is_some_and(|trait_did| tcx.lang_items().drop_trait() == Some(trait_did))
+    {
+        tcx.ensure_with_value().drop_impl_only_deallocates(impl_did);
+    }
     let mut body = tcx.mir_built(def).steal();
     if let Some(error_reported) = const_qualifs.tainted_by_errors {
         body.tainted_by_errors = Some(error_reported);
@@ -540,6 +551,9 @@ fn run_runtime_lowering_passes<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
 fn run_runtime_cleanup_passes<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
     let passes: &[&dyn MirPass<'tcx>] = &[
         &lower_intrinsics::LowerIntrinsics,
+        // Must come after `LowerIntrinsics`, which turns `ptr::write` into a
+        // plain store, and before inlining.
+        &add_gc_write_barriers::AddGcWriteBarriers,
         &remove_place_mention::RemovePlaceMention,
         &simplify::SimplifyCfg::PreOptimizations,
     ];
@@ -620,6 +634,8 @@ fn o1<T>(x: T) -> WithMinOptLevel<T> {
             // Must come before CriticalCallEdges to prevent LLVM basic block ordering errors.
             #[cfg(not(feature = "rustc_no_premopt"))]
             &remove_elidable_drops::RemoveElidableDrops,
+            #[cfg(not(feature = "rustc_no_premopt"))]
+            &place_gc_barriers::PlaceGcBarriers,
             // Some cleanup necessary at least for LLVM and potentially other codegen backends.
             &add_call_guards::CriticalCallEdges,
             // Cleanup for human readability, off by default.
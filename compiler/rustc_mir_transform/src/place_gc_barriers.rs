@@ -0,0 +1,479 @@
+//! Moves premature finalization barriers to the end of each `Gc`'s live range.
+//!
+//! Dropping a `Gc` runs a barrier which stops its referent from being finalized
+//! before that point. Drop elaboration puts those drops at the end of the
+//! `Gc`'s scope, which pins the pointer in a register or stack slot long after
+//! the last time it, or anything derived from it, is used. This is at its worst
+//! in loops, where a single `Gc` can stay live across the whole loop body.
+//!
+//! For each `Gc` local whose drops survive `RemoveElidableDrops`, this pass
+//! finds the points where the `Gc` and every value derived from it are dead,
+//! puts a single call to `std::gc::keep_alive` at each of them, and removes the
+//! scope-end drops. A value is derived from a `Gc` if it is computed from the
+//! `Gc` (or from another derived value) and may hold a pointer: a reference
+//! into the referent, a pointer the referent's finalizer could free, or a
+//! pointer-sized integer such as `Gc::as_ptr(&gc) as usize`.
+//!
+//! The analysis is deliberately conservative. A `Gc` is left alone if it is
+//! assigned more than once, mutably borrowed or moved, or if a derived value
+//! escapes into memory, into a call which could store it, or across the `Gc`'s
+//! own definition (as in a loop). Drops on unwind paths are never touched.
+
+use rustc_data_structures::fx::{FxIndexMap, FxIndexSet};
+use rustc_hir::def_id::DefId;
+use rustc_index::bit_set::BitSet;
+use rustc_middle::mir::visit::{MutatingUseContext, NonMutatingUseContext, PlaceContext, Visitor};
+use rustc_middle::mir::*;
+use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_mir_dataflow::impls::MaybeLiveLocals;
+use rustc_mir_dataflow::{Analysis, ResultsCursor};
+use rustc_session::Session;
+use rustc_span::source_map::Spanned;
+use rustc_span::sym;
+use rustc_target::abi::FieldIdx;
+
+use super::simplify::simplify_cfg;
+
+pub struct PlaceGcBarriers;
+
+/// A point at which a `Gc` and all of its derived values have just died.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum BarrierSite {
+    /// Immediately after the statement at this location.
+    Statement(Location),
+    /// On the control flow edge between two blocks.
+    Edge(BasicBlock, BasicBlock),
+}
+
+impl<'tcx> MirPass<'tcx> for PlaceGcBarriers {
+    fn is_enabled(&self, sess: &Session) -> bool {
+        // Unoptimized builds keep `Gc`s alive until the end of their scope,
+        // which is easier to reason about in a debugger.
+        sess.mir_opt_level() >= 2 && !sess.opts.cg.no_premature_finalizer_prevention_opt
+    }
+
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        let Some(keep_alive) = tcx.get_diagnostic_item(sym::gc_keep_alive) else {
+            return;
+        };
+        let drops = gc_drops(tcx, body);
+        if drops.is_empty() {
+            return;
+        }
+        trace!("Running PlaceGcBarriers on {:?}", body.source);
+
+        // Liveness is computed without the drops this pass may remove (they are
+        // only barriers, not real uses) and without unwind edges, because the
+        // drops in cleanup blocks are left where they are. The terminators are
+        // changed in place and put back once the barrier sites are known.
+        let mut original_terminators = Vec::new();
+        for (bb, data) in body.basic_blocks.as_mut().iter_enumerated_mut() {
+            let is_cleanup = data.is_cleanup;
+            let terminator = data.terminator_mut();
+            let original = terminator.kind.clone();
+            if let TerminatorKind::Drop { place, target, .. } = terminator.kind
+                && !is_cleanup
+                && place.as_local().is_some_and(|local| drops.contains_key(&local))
+            {
+                terminator.kind = TerminatorKind::Goto { target };
+            }
+            if let Some(unwind) = terminator.unwind_mut() {
+                *unwind = UnwindAction::Unreachable;
+            }
+            if terminator.kind != original {
+                original_terminators.push((bb, original));
+            }
+        }
+
+        let param_env = tcx.param_env_reveal_all_normalized(body.source.def_id());
+        let mut sites: FxIndexMap<BarrierSite, Vec<Local>> = FxIndexMap::default();
+        let mut removed = Vec::new();
+        {
+            let mut liveness =
+                MaybeLiveLocals.into_engine(tcx, body).iterate_to_fixpoint().into_results_cursor(&*body);
+            for (&local, blocks) in &drops {
+                let Some(local_sites) = barrier_sites(tcx, param_env, body, &mut liveness, local)
+                else {
+                    continue;
+                };
+                debug!("moving barriers for {:?} from {:?} to {:?}", local, blocks, local_sites);
+                for site in local_sites {
+                    sites.entry(site).or_default().push(local);
+                }
+                removed.extend_from_slice(blocks);
+            }
+        }
+        for (bb, original) in original_terminators {
+            body.basic_blocks.as_mut()[bb].terminator_mut().kind = original;
+        }
+        if removed.is_empty() {
+            return;
+        }
+
+        for bb in removed {
+            let terminator = body.basic_blocks.as_mut()[bb].terminator_mut();
+            let TerminatorKind::Drop { target, .. } = terminator.kind else { bug!() };
+            terminator.kind = TerminatorKind::Goto { target };
+        }
+
+        // Edges first, so that splitting blocks afterwards carries the
+        // redirected terminators along. Statements are then handled from the
+        // end of each block, so that earlier locations stay valid.
+        let (mut statements, edges): (Vec<_>, Vec<_>) =
+            sites.into_iter().partition(|(site, _)| matches!(site, BarrierSite::Statement(_)));
+        for (site, locals) in edges {
+            let BarrierSite::Edge(from, to) = site else { bug!() };
+            let source_info = body.basic_blocks[from].terminator().source_info;
+            let barriers = insert_barriers(tcx, body, keep_alive, &locals, source_info, to);
+            for succ in body.basic_blocks.as_mut()[from].terminator_mut().successors_mut() {
+                if *succ == to {
+                    *succ = barriers;
+                }
+            }
+        }
+        statements.sort_by_key(|(site, _)| match site {
+            BarrierSite::Statement(location) => std::cmp::Reverse(*location),
+            BarrierSite::Edge(..) => bug!(),
+        });
+        for (site, locals) in statements {
+            let BarrierSite::Statement(location) = site else { bug!() };
+            let block_data = &mut body.basic_blocks.as_mut()[location.block];
+            let source_info = block_data.statements[location.statement_index].source_info;
+            let rest = BasicBlockData {
+                statements: block_data.statements.split_off(location.statement_index + 1),
+                terminator: block_data.terminator.take(),
+                is_cleanup: block_data.is_cleanup,
+            };
+            let rest = body.basic_blocks.as_mut().push(rest);
+            let barriers = insert_barriers(tcx, body, keep_alive, &locals, source_info, rest);
+            body.basic_blocks.as_mut()[location.block].terminator =
+                Some(Terminator { source_info, kind: TerminatorKind::Goto { target: barriers } });
+        }
+
+        simplify_cfg(body);
+    }
+}
+
+/// Finds the drops of `Gc` locals outside of cleanup blocks.
+fn gc_drops<'tcx>(tcx: TyCtxt<'tcx>, body: &Body<'tcx>) -> FxIndexMap<Local, Vec<BasicBlock>> {
+    let mut drops: FxIndexMap<Local, Vec<BasicBlock>> = FxIndexMap::default();
+    for (bb, data) in body.basic_blocks.iter_enumerated() {
+        if data.is_cleanup {
+            continue;
+        }
+        if let TerminatorKind::Drop { place, .. } = data.terminator().kind
+            && let Some(local) = place.as_local()
+            && body.local_decls[local].ty.is_gc(tcx)
+        {
+            drops.entry(local).or_default().push(bb);
+        }
+    }
+    drops
+}
+
+/// Computes where the barrier for `gc` should go, or `None` if its drops have
+/// to stay where they are.
+fn barrier_sites<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    body: &Body<'tcx>,
+    liveness: &mut ResultsCursor<'_, 'tcx, MaybeLiveLocals>,
+    gc: Local,
+) -> Option<FxIndexSet<BarrierSite>> {
+    let def = single_def(body, gc)?;
+    let derived = derived_locals(tcx, param_env, body, gc)?;
+    let any_live = |state: &BitSet<Local>| derived.iter().any(|local| state.contains(local));
+
+    // A derived value which is live where `gc` is assigned belongs to an
+    // earlier value of `gc`, which the new barrier would not cover.
+    let (live_before_def, live_after_def) = match def {
+        DefLocation::Argument => {
+            liveness.seek_to_block_start(START_BLOCK);
+            (liveness.get().clone(), liveness.get().contains(gc))
+        }
+        DefLocation::Assignment(location) => {
+            liveness.seek_after_primary_effect(location);
+            let before = liveness.get().clone();
+            liveness.seek_before_primary_effect(location);
+            (before, liveness.get().contains(gc))
+        }
+        DefLocation::CallReturn { call, target } => {
+            liveness.seek_after_primary_effect(body.terminator_loc(call));
+            let before = liveness.get().clone();
+            let after = target.is_some_and(|target| {
+                liveness.seek_to_block_start(target);
+                liveness.get().contains(gc)
+            });
+            (before, after)
+        }
+    };
+    if derived.iter().any(|local| local != gc && live_before_def.contains(local)) {
+        return None;
+    }
+
+    let mut sites = FxIndexSet::default();
+    // A `Gc` which is never used still gets a barrier straight after it is
+    // defined.
+    if !live_after_def {
+        match def {
+            DefLocation::Assignment(location) => {
+                sites.insert(BarrierSite::Statement(location));
+            }
+            DefLocation::CallReturn { call, target: Some(target) } => {
+                sites.insert(BarrierSite::Edge(call, target));
+            }
+            _ => return None,
+        }
+    }
+
+    for (bb, data) in traversal::reachable(body) {
+        for statement_index in (0..data.statements.len()).rev() {
+            let location = Location { block: bb, statement_index };
+            liveness.seek_before_primary_effect(location);
+            if any_live(liveness.get()) {
+                continue;
+            }
+            liveness.seek_after_primary_effect(location);
+            if any_live(liveness.get()) {
+                sites.insert(BarrierSite::Statement(location));
+            }
+        }
+        liveness.seek_after_primary_effect(body.terminator_loc(bb));
+        if !any_live(liveness.get()) {
+            continue;
+        }
+        for succ in data.terminator().successors() {
+            liveness.seek_to_block_start(succ);
+            if !any_live(liveness.get()) {
+                sites.insert(BarrierSite::Edge(bb, succ));
+            }
+        }
+    }
+
+    // The barrier reads `gc`, so it must have been assigned on every path to
+    // the barrier.
+    let dominators = body.basic_blocks.dominators();
+    let dominates = |site: &BarrierSite| match *site {
+        BarrierSite::Statement(location) => def.dominates(location, dominators),
+        BarrierSite::Edge(from, _) => def.dominates(body.terminator_loc(from), dominators),
+    };
+    sites.iter().all(dominates).then_some(sites)
+}
+
+/// Returns where `gc` is defined, provided that it is assigned exactly once and
+/// is never moved out of or modified in place.
+fn single_def(body: &Body<'_>, gc: Local) -> Option<DefLocation> {
+    struct Defs {
+        gc: Local,
+        defs: Vec<DefLocation>,
+        ok: bool,
+    }
+
+    impl<'tcx> Visitor<'tcx> for Defs {
+        fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, location: Location) {
+            // Writes through the referent don't change `gc` itself.
+            if place.local != self.gc || place.is_indirect() {
+                return;
+            }
+            match context {
+                PlaceContext::MutatingUse(MutatingUseContext::Store)
+                    if place.projection.is_empty() =>
+                {
+                    self.defs.push(DefLocation::Assignment(location))
+                }
+                // Recorded by `visit_terminator`.
+                PlaceContext::MutatingUse(MutatingUseContext::Call)
+                    if place.projection.is_empty() => {}
+                PlaceContext::MutatingUse(MutatingUseContext::Drop)
+                | PlaceContext::NonMutatingUse(
+                    NonMutatingUseContext::Copy
+                    | NonMutatingUseContext::Inspect
+                    | NonMutatingUseContext::SharedBorrow
+                    | NonMutatingUseContext::PlaceMention,
+                )
+                | PlaceContext::NonUse(_) => (),
+                _ => self.ok = false,
+            }
+        }
+
+        fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location) {
+            if let TerminatorKind::Call { destination, target, .. } = terminator.kind
+                && destination.as_local() == Some(self.gc)
+            {
+                self.defs.push(DefLocation::CallReturn { call: location.block, target });
+            }
+            self.super_terminator(terminator, location);
+        }
+    }
+
+    let mut visitor = Defs { gc, defs: Vec::new(), ok: true };
+    if body.args_iter().any(|arg| arg == gc) {
+        visitor.defs.push(DefLocation::Argument);
+    }
+    visitor.visit_body(body);
+    match visitor.defs[..] {
+        [def] if visitor.ok => Some(def),
+        _ => None,
+    }
+}
+
+/// Collects `gc` and the locals derived from it, or returns `None` if a
+/// derived value may escape somewhere its liveness can't be tracked.
+fn derived_locals<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    body: &Body<'tcx>,
+    gc: Local,
+) -> Option<BitSet<Local>> {
+    let mut derived = BitSet::new_empty(body.local_decls.len());
+    derived.insert(gc);
+
+    // Integers as wide as a pointer count too: `Gc::as_ptr(&gc) as usize`
+    // keeps the referent reachable just as well as the pointer does.
+    let holds_pointer = |ty: Ty<'tcx>| ty.may_contain_pointers(tcx, param_env);
+    let mut changed = true;
+    while changed {
+        changed = false;
+        let mut derive = |derived: &mut BitSet<Local>, place: Place<'tcx>| {
+            if place.is_indirect() {
+                return false;
+            }
+            if holds_pointer(body.local_decls[place.local].ty) {
+                changed |= derived.insert(place.local);
+            }
+            true
+        };
+        for (bb, data) in body.basic_blocks.iter_enumerated() {
+            if data.is_cleanup {
+                continue;
+            }
+            for (statement_index, statement) in data.statements.iter().enumerate() {
+                let location = Location { block: bb, statement_index };
+                match &statement.kind {
+                    StatementKind::Assign(box (place, rvalue)) => {
+                        let mut mentions = Mentions { locals: &derived, found: false };
+                        mentions.visit_rvalue(rvalue, location);
+                        if mentions.found && !derive(&mut derived, *place) {
+                            return None;
+                        }
+                    }
+                    StatementKind::Intrinsic(box NonDivergingIntrinsic::CopyNonOverlapping(..)) => {
+                        let mut mentions = Mentions { locals: &derived, found: false };
+                        mentions.visit_statement(statement, location);
+                        if mentions.found {
+                            return None;
+                        }
+                    }
+                    _ => (),
+                }
+            }
+
+            let location = body.terminator_loc(bb);
+            let terminator = data.terminator();
+            match &terminator.kind {
+                TerminatorKind::Call { func, args, destination, .. } => {
+                    let mentioned = |operand: &Operand<'tcx>| {
+                        let mut mentions = Mentions { locals: &derived, found: false };
+                        mentions.visit_operand(operand, location);
+                        mentions.found
+                    };
+                    if !mentioned(func) && !args.iter().any(|arg| mentioned(&arg.node)) {
+                        continue;
+                    }
+                    // The callee could store a derived value behind any other
+                    // pointer it is given.
+                    if args
+                        .iter()
+                        .any(|arg| !mentioned(&arg.node) && holds_pointer(arg.node.ty(body, tcx)))
+                    {
+                        return None;
+                    }
+                    if !derive(&mut derived, *destination) {
+                        return None;
+                    }
+                }
+                TerminatorKind::InlineAsm { .. } | TerminatorKind::Yield { .. } => {
+                    let mut mentions = Mentions { locals: &derived, found: false };
+                    mentions.visit_terminator(terminator, location);
+                    if mentions.found {
+                        return None;
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+    Some(derived)
+}
+
+struct Mentions<'a> {
+    locals: &'a BitSet<Local>,
+    found: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for Mentions<'_> {
+    fn visit_local(&mut self, local: Local, _: PlaceContext, _: Location) {
+        self.found |= self.locals.contains(local);
+    }
+}
+
+/// Builds a chain of blocks calling `keep_alive` on each of `gcs`, which then
+/// continues to `target`. Returns the first block of the chain.
+fn insert_barriers<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &mut Body<'tcx>,
+    keep_alive: DefId,
+    gcs: &[Local],
+    source_info: SourceInfo,
+    target: BasicBlock,
+) -> BasicBlock {
+    let mut next = target;
+    for &gc in gcs.iter().rev() {
+        // `Gc<T>` holds a `NonNull<GcBox<T>>`, which in turn holds the
+        // `*const GcBox<T>` that `keep_alive` needs.
+        let ty::Adt(gc_def, gc_args) = *body.local_decls[gc].ty.kind() else { bug!() };
+        let non_null_ty = gc_def.non_enum_variant().fields[FieldIdx::ZERO].ty(tcx, gc_args);
+        let ty::Adt(non_null_def, non_null_args) = *non_null_ty.kind() else { bug!() };
+        let ptr_ty = non_null_def.non_enum_variant().fields[FieldIdx::ZERO].ty(tcx, non_null_args);
+        let box_ty = ptr_ty.builtin_deref(true).unwrap().ty;
+        let ptr = tcx.mk_place_field(
+            tcx.mk_place_field(gc.into(), FieldIdx::ZERO, non_null_ty),
+            FieldIdx::ZERO,
+            ptr_ty,
+        );
+
+        let mut_ptr_ty = Ty::new_mut_ptr(tcx, box_ty);
+        let mut_ptr = body.local_decls.push(LocalDecl::with_source_info(mut_ptr_ty, source_info));
+        let unit = body.local_decls.push(LocalDecl::with_source_info(tcx.types.unit, source_info));
+        let cast = Rvalue::Cast(CastKind::PtrToPtr, Operand::Copy(ptr), mut_ptr_ty);
+        next = body.basic_blocks.as_mut().push(BasicBlockData {
+            statements: vec![Statement {
+                source_info,
+                kind: StatementKind::Assign(Box::new((mut_ptr.into(), cast))),
+            }],
+            terminator: Some(Terminator {
+                source_info,
+                kind: TerminatorKind::Call {
+                    func: Operand::function_handle(
+                        tcx,
+                        keep_alive,
+                        [box_ty.into()],
+                        source_info.span,
+                    ),
+                    args: vec![Spanned {
+                        node: Operand::Move(mut_ptr.into()),
+                        span: source_info.span,
+                    }],
+                    destination: unit.into(),
+                    target: Some(next),
+                    // `keep_alive` is `#[rustc_nounwind]`.
+                    unwind: UnwindAction::Unreachable,
+                    call_source: CallSource::Misc,
+                    fn_span: source_info.span,
+                },
+            }),
+            is_cleanup: false,
+        });
+    }
+    next
+}
@@ -1,8 +1,10 @@
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::sync::Lock;
 use rustc_span::def_id::DefId;
-use rustc_span::Symbol;
+use rustc_span::source_map::SourceMap;
+use rustc_span::{Span, Symbol};
 use rustc_target::abi::{Align, Size};
+use serde::Serialize;
 use std::cmp;
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
@@ -89,10 +91,72 @@ pub struct VTableSizeInfo {
     pub upcasting_cost_percent: f64,
 }
 
+/// Why a `Gc<T>` was constructed without registering a finalizer for `T`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FinalizerElision {
+    /// `T` has no drop glue at all.
+    NoDropGlue,
+    /// `T` is wrapped in a `NonFinalizable`.
+    NonFinalizable,
+    /// Every drop method in `T`'s drop glue belongs to a type which implements
+    /// `DropMethodFinalizerElidable`, and none of their components need finalizing.
+    DropMethodFinalizerElidable,
+    /// As `DropMethodFinalizerElidable`, but at least one of the drop methods was
+    /// inferred to do nothing but deallocate memory and drop the values it owns.
+    DropMethodOnlyDeallocates,
+    /// `T`'s finalizer is only elided because `GcAllocator` is the global allocator
+    /// and `T` contains a `FinalizerElidableWithGcAllocator` type (e.g. `Rc`).
+    FinalizerElidableWithGcAllocator,
+}
+
+/// The result of running Finalizer Safety Analysis (FSA) on a `Gc<T>` entry point.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum FsaOutcome {
+    /// FSA was disabled with `-C no-finalizer-safety-analysis`.
+    Disabled,
+    /// `T` does not need a finalizer, so there was nothing to check.
+    NoFinalizer,
+    /// FSA was waived because `T` is wrapped in a `FinalizeUnchecked`.
+    FinalizeUnchecked,
+    /// Every drop method in `T`'s drop glue is safe to run as a finalizer.
+    Passed,
+    /// FSA emitted this many errors.
+    Failed(usize),
+}
+
+impl FsaOutcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FsaOutcome::Disabled => "disabled",
+            FsaOutcome::NoFinalizer => "no_finalizer",
+            FsaOutcome::FinalizeUnchecked => "finalize_unchecked",
+            FsaOutcome::Passed => "passed",
+            FsaOutcome::Failed(_) => "failed",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct GcReportInfo {
+    /// The `Gc<T>` type constructed at this entry point.
+    pub gc_ty: String,
+    /// Span of the call to the entry point (e.g. `Gc::new(..)`).
+    pub fn_span: Span,
+    /// Span of the value passed to the entry point.
+    pub arg_span: Span,
+    pub needs_finalizer: bool,
+    pub elision: Option<FinalizerElision>,
+    pub fsa: FsaOutcome,
+    /// The drop methods which FSA checked for this entry point, in the order they were visited.
+    pub drop_methods: Vec<String>,
+}
+
 #[derive(Default)]
 pub struct CodeStats {
     type_sizes: Lock<FxHashSet<TypeSizeInfo>>,
     vtable_sizes: Lock<FxHashMap<DefId, VTableSizeInfo>>,
+    gc_reports: Lock<Vec<GcReportInfo>>,
 }
 
 impl CodeStats {
@@ -134,6 +198,10 @@ pub fn record_vtable_size(&self, trait_did: DefId, trait_name: &str, info: VTabl
         );
     }
 
+    pub fn record_gc_report(&self, info: GcReportInfo) {
+        self.gc_reports.lock().push(info);
+    }
+
     pub fn print_type_sizes(&self) {
         let type_sizes = self.type_sizes.borrow();
         // We will soon sort, so the initial order does not matter.
@@ -266,4 +334,49 @@ pub fn print_vtable_sizes(&self, crate_name: Symbol) {
             );
         }
     }
+
+    pub fn print_gc_report(&self, crate_name: Symbol, source_map: &SourceMap) {
+        #[derive(Serialize)]
+        struct GcReport<'a> {
+            crate_name: &'a str,
+            gc_entry_points: Vec<GcEntryPoint>,
+        }
+
+        #[derive(Serialize)]
+        struct GcEntryPoint {
+            #[serde(rename = "type")]
+            gc_ty: String,
+            fn_span: String,
+            arg_span: String,
+            needs_finalizer: bool,
+            elision_reason: Option<FinalizerElision>,
+            fsa: &'static str,
+            fsa_errors: usize,
+            drop_methods: Vec<String>,
+        }
+
+        let mut reports = std::mem::take(&mut *self.gc_reports.lock());
+        // MIR bodies may be checked in any order, so sort by location to keep the output stable
+        // between runs.
+        reports.sort_by_key(|info| (info.fn_span, info.arg_span));
+
+        let gc_entry_points = reports
+            .into_iter()
+            .map(|info| GcEntryPoint {
+                gc_ty: info.gc_ty,
+                fn_span: source_map.span_to_embeddable_string(info.fn_span),
+                arg_span: source_map.span_to_embeddable_string(info.arg_span),
+                needs_finalizer: info.needs_finalizer,
+                elision_reason: info.elision,
+                fsa: info.fsa.as_str(),
+                fsa_errors: match info.fsa {
+                    FsaOutcome::Failed(n) => n,
+                    _ => 0,
+                },
+                drop_methods: info.drop_methods,
+            })
+            .collect();
+        let report = GcReport { crate_name: crate_name.as_str(), gc_entry_points };
+        println!("{}", serde_json::to_string_pretty(&report).unwrap());
+    }
 }
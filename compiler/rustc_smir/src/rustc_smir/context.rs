@@ -500,6 +500,12 @@ fn is_empty_drop_shim(&self, def: InstanceDef) -> bool {
         matches!(instance.def, ty::InstanceDef::DropGlue(_, None))
     }
 
+    fn is_fsa_entry_point(&self, def: InstanceDef) -> bool {
+        let tables = self.0.borrow_mut();
+        let instance = tables.instances[def];
+        tables.tcx.registers_finalizer(ty::ParamEnv::reveal_all().and(instance))
+    }
+
     fn mono_instance(&self, def_id: stable_mir::DefId) -> stable_mir::mir::mono::Instance {
         let mut tables = self.0.borrow_mut();
         let def_id = tables[def_id];
@@ -657,6 +663,30 @@ fn ty_layout(&self, ty: Ty) -> Result<Layout, Error> {
         Ok(layout.stable(&mut *tables))
     }
 
+    fn ty_is_gc(&self, ty: Ty) -> bool {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        ty.internal(&mut *tables, tcx).is_gc(tcx)
+    }
+
+    fn ty_needs_finalizer(&self, ty: Ty) -> bool {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        ty.internal(&mut *tables, tcx).needs_finalizer(tcx, tables.param_env())
+    }
+
+    fn ty_is_finalizer_safe(&self, ty: Ty) -> bool {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        ty.internal(&mut *tables, tcx).is_finalizer_safe(tcx, tables.param_env())
+    }
+
+    fn ty_drop_method_finalizer_elidable(&self, ty: Ty) -> bool {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        ty.internal(&mut *tables, tcx).drop_method_finalizer_elidable(tcx, tables.param_env())
+    }
+
     fn layout_shape(&self, id: Layout) -> LayoutShape {
         let mut tables = self.0.borrow_mut();
         let tcx = tables.tcx;
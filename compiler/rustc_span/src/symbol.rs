@@ -129,6 +129,7 @@
         Abi,
         AcqRel,
         Acquire,
+        Allocator,
         Any,
         Arc,
         ArcWeak,
@@ -212,6 +213,7 @@
         FusedIterator,
         Future,
         FutureOutput,
+        GcAllocator,
         GlobalAlloc,
         Hash,
         HashMap,
@@ -370,6 +372,7 @@
         alignment,
         all,
         alloc,
+        alloc_atomic,
         alloc_error_handler,
         alloc_layout,
         alloc_zeroed,
@@ -842,6 +845,7 @@
         field,
         field_init_shorthand,
         file,
+        finalizer_elidable_with_gc_allocator,
         float,
         float_to_int_unchecked,
         floorf128,
@@ -901,6 +905,10 @@
         future,
         future_trait,
         gc,
+        gc_guardian_register,
+        gc_keep_alive,
+        gc_scanned_allocator,
+        gc_write_barrier,
         gdb_script_file,
         ge,
         gen_blocks,
@@ -1117,6 +1125,7 @@
         maxnumf16,
         maxnumf32,
         maxnumf64,
+        may_contain_pointers,
         may_dangle,
         may_unwind,
         maybe_uninit,
@@ -1219,6 +1228,7 @@
         needs_allocator,
         needs_drop,
         needs_finalizer,
+        needs_finalizer_with_gc_allocator,
         needs_panic_runtime,
         neg,
         negate_unsigned,
@@ -1466,6 +1476,7 @@
         read_via_copy,
         readonly,
         realloc,
+        realloc_scanned,
         reason,
         receiver,
         recursion_limit,
@@ -1579,6 +1590,7 @@
         rustc_expected_cgu_reuse,
         rustc_fsa_entry_point,
         rustc_fsa_safe_fn,
+        rustc_global_allocator,
         rustc_has_incoherent_inherent_impls,
         rustc_hidden_type_of_opaques,
         rustc_if_this_changed,
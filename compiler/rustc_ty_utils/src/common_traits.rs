@@ -30,6 +30,13 @@ fn drop_method_finalizer_elidable_raw<'tcx>(
     is_item_raw(tcx, query, LangItem::DropMethodFinalizerElidable)
 }
 
+fn finalizer_elidable_with_gc_allocator_raw<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    query: ty::ParamEnvAnd<'tcx, Ty<'tcx>>,
+) -> bool {
+    is_item_raw(tcx, query, LangItem::FinalizerElidableWithGcAllocator)
+}
+
 fn is_finalizer_safe_raw<'tcx>(tcx: TyCtxt<'tcx>, query: ty::ParamEnvAnd<'tcx, Ty<'tcx>>) -> bool {
     is_diagnostic_item_raw(tcx, query, sym::FinalizerSafe)
 }
@@ -74,6 +81,7 @@ pub(crate) fn provide(providers: &mut Providers) {
         is_freeze_raw,
         is_unpin_raw,
         drop_method_finalizer_elidable_raw,
+        finalizer_elidable_with_gc_allocator_raw,
         ..*providers
     };
 }
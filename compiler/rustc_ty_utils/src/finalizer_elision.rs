@@ -0,0 +1,285 @@
+//! Infer whether a drop method can be elided when it is run as a finalizer.
+//!
+//! A drop method which does nothing but drop the values it owns and hand
+//! memory back to the global allocator is redundant inside a finalizer: with
+//! `GcAllocator` as the global allocator, which `Gc` requires, the collector
+//! reclaims that memory anyway. Such types are treated as though they
+//! implemented `DropMethodFinalizerElidable`, without their authors having to
+//! opt in.
+//!
+//! Each `Drop` impl is judged once, for all of its instances, on the built MIR
+//! of its drop method in the crate which defines it. Other crates read that
+//! answer from the defining crate's metadata instead of judging for themselves.
+//!
+//! Types implementing `FinalizerElidableWithGcAllocator` are not inferred
+//! here; see that trait.
+
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
+use rustc_middle::mir::visit::Visitor;
+use rustc_middle::mir::{
+    Body, Local, Location, NonDivergingIntrinsic, Place, ProjectionElem, Statement, StatementKind,
+    Terminator, TerminatorKind,
+};
+use rustc_middle::query::{LocalCrate, Providers};
+use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_span::{sym, Symbol};
+
+/// The maximum number of function bodies inspected for a single drop method.
+/// Anything larger is conservatively assumed to do more than deallocate.
+const MAX_BODIES: usize = 64;
+
+/// Intrinsics without side effects which may appear in a deallocating drop
+/// method (mostly through layout computations and debug assertions).
+const PURE_INTRINSICS: &[Symbol] = &[
+    sym::assert_inhabited,
+    sym::assert_mem_uninitialized_valid,
+    sym::assert_zero_valid,
+    sym::assume,
+    sym::min_align_of,
+    sym::min_align_of_val,
+    sym::needs_drop,
+    sym::size_of,
+    sym::size_of_val,
+    sym::ub_checks,
+];
+
+fn global_allocator_is_gc(tcx: TyCtxt<'_>, _: LocalCrate) -> bool {
+    let Some(gc_allocator) = tcx.get_diagnostic_item(sym::GcAllocator) else {
+        return false;
+    };
+    tcx.hir_crate_items(()).definitions().any(|def_id| {
+        matches!(tcx.def_kind(def_id), DefKind::Static { .. })
+            && tcx.has_attr(def_id, sym::rustc_global_allocator)
+            && tcx
+                .type_of(def_id)
+                .instantiate_identity()
+                .ty_adt_def()
+                .is_some_and(|adt| adt.did() == gc_allocator)
+    })
+}
+
+fn drop_method_only_deallocates_raw<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    query: ty::ParamEnvAnd<'tcx, Ty<'tcx>>,
+) -> bool {
+    let ty::Adt(adt_def, _) = *query.value.kind() else {
+        return false;
+    };
+    let Some(destructor) = adt_def.destructor(tcx) else {
+        return false;
+    };
+    tcx.drop_impl_only_deallocates(tcx.parent(destructor.did))
+}
+
+/// Judges a `Drop` impl once, for all of its instances, in the crate which
+/// defines it. Other crates read the answer from its metadata, so every crate
+/// agrees on it.
+fn drop_impl_only_deallocates(tcx: TyCtxt<'_>, impl_did: LocalDefId) -> bool {
+    let ty::Adt(adt_def, args) = *tcx.type_of(impl_did).instantiate_identity().kind() else {
+        return false;
+    };
+    // `Box`'s drop method is empty: the deallocation is filled in by drop
+    // elaboration, so there is nothing here to infer from.
+    if adt_def.is_box() {
+        return false;
+    }
+    let Some(destructor) = adt_def.destructor(tcx) else {
+        return false;
+    };
+    let param_env = tcx.param_env(impl_did);
+
+    // The drop method may drop anything that `needs_finalizer` will go on to
+    // check itself: the ADT's type arguments and its fields.
+    let owned_tys: FxHashSet<_> = args
+        .types()
+        .chain(adt_def.all_fields().map(|field| tcx.type_of(field.did).instantiate(tcx, args)))
+        .map(|ty| tcx.try_normalize_erasing_regions(param_env, ty).unwrap_or(ty))
+        .collect();
+
+    let drop_instance = ty::Instance::new(
+        destructor.did,
+        ty::GenericArgs::identity_for_item(tcx, destructor.did),
+    );
+    let mut deallocates = false;
+    let mut seen = FxHashSet::default();
+    let mut queue = vec![drop_instance];
+    seen.insert(drop_instance);
+    while let Some(instance) = queue.pop() {
+        // Only the drop method itself can be local (see `DeallocOnly::check_call`). It
+        // is judged on its built MIR, as finalizer safety analysis asks about this impl
+        // while the drop method's own MIR is being promoted.
+        let built;
+        let body = match instance.def_id().as_local() {
+            Some(local) => {
+                built = tcx.mir_built(local).borrow();
+                &*built
+            }
+            None if seen.len() <= MAX_BODIES && tcx.is_mir_available(instance.def_id()) => {
+                tcx.instance_mir(instance.def)
+            }
+            None => return false,
+        };
+
+        let mut checker = DeallocOnly {
+            tcx,
+            param_env,
+            instance,
+            body,
+            owned_tys: &owned_tys,
+            is_drop_method: instance == drop_instance,
+            callees: Vec::new(),
+            deallocates: false,
+            ok: true,
+        };
+        checker.visit_body(body);
+        if !checker.ok {
+            debug!("drop_impl_only_deallocates({:?}): rejected by {:?}", impl_did, instance);
+            return false;
+        }
+        deallocates |= checker.deallocates;
+        for callee in checker.callees {
+            if seen.insert(callee) {
+                queue.push(callee);
+            }
+        }
+    }
+    // A drop method which never deallocates has nothing for the collector to
+    // take over. Empty drop methods are mostly there to stop a type being
+    // destructured, and their authors can opt in if they want elision.
+    deallocates
+}
+
+/// Checks that a single body has no side effects other than dropping owned
+/// values and deallocating memory. Calls to other functions are collected in
+/// `callees` so that they can be checked in turn.
+struct DeallocOnly<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    /// The instance whose body is being checked, whose arguments its types are
+    /// instantiated with.
+    instance: ty::Instance<'tcx>,
+    body: &'a Body<'tcx>,
+    owned_tys: &'a FxHashSet<Ty<'tcx>>,
+    /// Whether this is the body of `Drop::drop` itself, rather than a callee.
+    is_drop_method: bool,
+    callees: Vec<ty::Instance<'tcx>>,
+    /// Whether this body calls the allocator to free memory.
+    deallocates: bool,
+    ok: bool,
+}
+
+impl<'a, 'tcx> DeallocOnly<'a, 'tcx> {
+    fn monomorphize(&self, ty: Ty<'tcx>) -> Option<Ty<'tcx>> {
+        self.instance
+            .try_instantiate_mir_and_normalize_erasing_regions(
+                self.tcx,
+                self.param_env,
+                ty::EarlyBinder::bind(ty),
+            )
+            .ok()
+    }
+
+    /// Writes to locals are invisible outside of the body. The only memory the
+    /// drop method may write to is the value being dropped, through `self`.
+    fn check_write(&mut self, place: Place<'tcx>) {
+        let derefs = place.projection.iter().filter(|p| matches!(p, ProjectionElem::Deref));
+        let writes_self = self.is_drop_method
+            && place.local == Local::from_u32(1)
+            && matches!(place.projection.first(), Some(ProjectionElem::Deref));
+        match derefs.count() {
+            0 => (),
+            1 if writes_self => (),
+            _ => self.ok = false,
+        }
+    }
+
+    /// Dropping a value is fine if it is one that `needs_finalizer` checks
+    /// separately, or if it has no drop glue at all.
+    fn is_owned(&self, ty: Ty<'tcx>) -> bool {
+        if self.owned_tys.contains(&ty) || !ty.needs_drop(self.tcx, self.param_env) {
+            return true;
+        }
+        match *ty.kind() {
+            ty::Slice(elem) | ty::Array(elem, _) => self.is_owned(elem),
+            ty::Tuple(tys) => tys.iter().all(|ty| self.is_owned(ty)),
+            _ => false,
+        }
+    }
+
+    fn check_call(&mut self, fn_did: DefId, args: ty::GenericArgsRef<'tcx>) {
+        let tcx = self.tcx;
+        if tcx.codegen_fn_attrs(fn_did).flags.contains(CodegenFnAttrFlags::DEALLOCATOR) {
+            self.deallocates = true;
+            return;
+        }
+        // Panicking in a finalizer is no more useful than not running it at
+        // all. Diverging functions in `core` can do nothing else, except for
+        // intrinsics such as `abort`, which are checked below. Others, such as
+        // `process::exit`, may have side effects before they diverge.
+        if tcx.fn_sig(fn_did).skip_binder().output().skip_binder().is_never()
+            && tcx.crate_name(fn_did.krate) == sym::core
+            && tcx.intrinsic(fn_did).is_none()
+        {
+            return;
+        }
+        match ty::Instance::resolve(tcx, self.param_env, fn_did, args) {
+            Ok(Some(instance)) => match instance.def {
+                ty::InstanceDef::Intrinsic(did) => {
+                    self.ok &= PURE_INTRINSICS.contains(&tcx.item_name(did));
+                }
+                ty::InstanceDef::DropGlue(_, None) => (),
+                ty::InstanceDef::DropGlue(_, Some(ty)) => self.ok &= self.is_owned(ty),
+                // Local functions may be in the middle of being built.
+                ty::InstanceDef::Item(did) if !did.is_local() => self.callees.push(instance),
+                _ => self.ok = false,
+            },
+            _ => self.ok = false,
+        }
+    }
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DeallocOnly<'a, 'tcx> {
+    fn visit_statement(&mut self, statement: &Statement<'tcx>, _: Location) {
+        match &statement.kind {
+            StatementKind::Assign(box (place, _))
+            | StatementKind::SetDiscriminant { box place, .. }
+            | StatementKind::Deinit(box place) => self.check_write(*place),
+            StatementKind::Intrinsic(box NonDivergingIntrinsic::CopyNonOverlapping(..)) => {
+                self.ok = false
+            }
+            _ => (),
+        }
+    }
+
+    fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, _: Location) {
+        match &terminator.kind {
+            TerminatorKind::Call { func, destination, .. } => {
+                self.check_write(*destination);
+                match self.monomorphize(func.ty(self.body, self.tcx)).map(|ty| *ty.kind()) {
+                    Some(ty::FnDef(fn_did, args)) => self.check_call(fn_did, args),
+                    _ => self.ok = false,
+                }
+            }
+            TerminatorKind::Drop { place, .. } => {
+                let ty = self.monomorphize(place.ty(self.body, self.tcx).ty);
+                self.ok &= ty.is_some_and(|ty| self.is_owned(ty));
+            }
+            TerminatorKind::InlineAsm { .. }
+            | TerminatorKind::Yield { .. }
+            | TerminatorKind::CoroutineDrop => self.ok = false,
+            _ => (),
+        }
+    }
+}
+
+pub(crate) fn provide(providers: &mut Providers) {
+    *providers = Providers {
+        global_allocator_is_gc,
+        drop_method_only_deallocates_raw,
+        drop_impl_only_deallocates,
+        ..*providers
+    };
+}
@@ -0,0 +1,32 @@
+//! Find out whether a crate uses `std::gc::Gc`.
+//!
+//! This is recorded in each crate's metadata so that the crate which generates
+//! the allocator shim can tell whether anything in the crate graph relies on
+//! the global allocator being `GcAllocator`.
+
+use rustc_middle::query::{LocalCrate, Providers};
+use rustc_middle::ty::TyCtxt;
+use rustc_span::sym;
+
+fn crate_uses_gc(tcx: TyCtxt<'_>, _: LocalCrate) -> bool {
+    let Some(gc) = tcx.get_diagnostic_item(sym::gc) else {
+        return false;
+    };
+    // The crate defining `Gc` is linked into every program, whether it uses
+    // `Gc` or not.
+    if gc.is_local() {
+        return false;
+    }
+    tcx.hir().body_owners().any(|def_id| {
+        !tcx.is_typeck_child(def_id.to_def_id())
+            && tcx
+                .typeck(def_id)
+                .node_types()
+                .items()
+                .any(|(_, ty)| ty.walk().any(|arg| arg.as_type().is_some_and(|ty| ty.is_gc(tcx))))
+    })
+}
+
+pub(crate) fn provide(providers: &mut Providers) {
+    *providers = Providers { crate_uses_gc, ..*providers };
+}
@@ -28,6 +28,8 @@
 mod common_traits;
 mod consts;
 mod errors;
+mod finalizer_elision;
+mod gc;
 mod implied_bounds;
 mod instance;
 mod layout;
@@ -46,6 +48,8 @@ pub fn provide(providers: &mut Providers) {
     assoc::provide(providers);
     common_traits::provide(providers);
     consts::provide(providers);
+    finalizer_elision::provide(providers);
+    gc::provide(providers);
     implied_bounds::provide(providers);
     layout::provide(providers);
     needs_drop::provide(providers);
@@ -190,7 +190,11 @@ fn next(&mut self) -> Option<NeedsDropResult<Ty<'tcx>>> {
                     // impl then check whether the field types need `Drop`.
                     ty::Adt(adt_def, args) => {
                         let drop_method_finalizer_elidable = self.analysis_kind.is_finalization()
-                            && component.drop_method_finalizer_elidable(tcx, self.param_env);
+                            && (component.drop_method_finalizer_elidable(tcx, self.param_env)
+                                || component.drop_method_only_deallocates(tcx, self.param_env)
+                                || (self.analysis_kind.with_gc_allocator()
+                                    && component
+                                        .finalizer_elidable_with_gc_allocator(tcx, self.param_env)));
 
                         if self.analysis_kind.is_finalization() {
                             self.analysis_kind.cache_type(component);
@@ -287,7 +291,8 @@ enum DtorType {
 
 enum AnalysisKind<'tcx> {
     Destruction,
-    Finalization(FxHashSet<Ty<'tcx>>),
+    /// The flag is set if `GcAllocator` is assumed to be the global allocator.
+    Finalization(FxHashSet<Ty<'tcx>>, bool),
 }
 
 impl<'tcx> AnalysisKind<'tcx> {
@@ -298,8 +303,12 @@ fn is_finalization(&self) -> bool {
         }
     }
 
+    fn with_gc_allocator(&self) -> bool {
+        matches!(self, AnalysisKind::Finalization(_, true))
+    }
+
     fn cache_type(&mut self, ty: Ty<'tcx>) {
-        if let AnalysisKind::Finalization(cache) = self {
+        if let AnalysisKind::Finalization(cache, _) = self {
             cache.insert(ty);
         } else {
             bug!("Cannot cache types for destruction analysis");
@@ -307,7 +316,7 @@ fn cache_type(&mut self, ty: Ty<'tcx>) {
     }
 
     fn is_cached(&mut self, ty: Ty<'tcx>) -> bool {
-        if let AnalysisKind::Finalization(cache) = self {
+        if let AnalysisKind::Finalization(cache, _) = self {
             return cache.contains(&ty);
         } else {
             bug!("Cannot cache types for destruction analysis");
@@ -456,6 +465,25 @@ fn adt_significant_drop_tys(
 }
 
 fn needs_finalizer_raw<'tcx>(tcx: TyCtxt<'tcx>, query: ty::ParamEnvAnd<'tcx, Ty<'tcx>>) -> bool {
+    let res = needs_finalizer_helper(tcx, query, false);
+    debug!("needs_finalizer_raw({:?}) = {:?}", query, res);
+    res
+}
+
+fn needs_finalizer_with_gc_allocator_raw<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    query: ty::ParamEnvAnd<'tcx, Ty<'tcx>>,
+) -> bool {
+    let res = needs_finalizer_helper(tcx, query, true);
+    debug!("needs_finalizer_with_gc_allocator_raw({:?}) = {:?}", query, res);
+    res
+}
+
+fn needs_finalizer_helper<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    query: ty::ParamEnvAnd<'tcx, Ty<'tcx>>,
+    with_gc_allocator: bool,
+) -> bool {
     let adt_has_dtor =
         |adt_def: ty::AdtDef<'tcx>| adt_def.destructor(tcx).map(|_| DtorType::Significant);
 
@@ -486,18 +514,15 @@ fn needs_finalizer_raw<'tcx>(tcx: TyCtxt<'tcx>, query: ty::ParamEnvAnd<'tcx, Ty<
         .map(|v| v.into_iter())
     };
 
-    let res = NeedsDropTypes::new(
+    NeedsDropTypes::new(
         tcx,
         query.param_env,
         query.value,
         adt_components,
-        AnalysisKind::Finalization(FxHashSet::default()),
+        AnalysisKind::Finalization(FxHashSet::default(), with_gc_allocator),
     )
     .next()
-    .is_some();
-
-    debug!("needs_finalizer_raw({:?}) = {:?}", query, res);
-    res
+    .is_some()
 }
 
 pub(crate) fn provide(providers: &mut Providers) {
@@ -505,6 +530,7 @@ pub(crate) fn provide(providers: &mut Providers) {
         needs_drop_raw,
         has_significant_drop_raw,
         needs_finalizer_raw,
+        needs_finalizer_with_gc_allocator_raw,
         adt_drop_tys,
         adt_significant_drop_tys,
         ..*providers
@@ -158,6 +158,9 @@ pub trait Context {
     /// Check if this is an empty DropGlue shim.
     fn is_empty_drop_shim(&self, def: InstanceDef) -> bool;
 
+    /// Check if this instance is a `Gc` constructor that finalizer safety analysis checks.
+    fn is_fsa_entry_point(&self, def: InstanceDef) -> bool;
+
     /// Convert a non-generic crate item into an instance.
     /// This function will panic if the item is generic.
     fn mono_instance(&self, def_id: DefId) -> Instance;
@@ -206,6 +209,18 @@ fn resolve_closure(
     /// Get the layout of a type.
     fn ty_layout(&self, ty: Ty) -> Result<Layout, Error>;
 
+    /// Check if the type is `Gc<T>`.
+    fn ty_is_gc(&self, ty: Ty) -> bool;
+
+    /// Check if dropping a value of this type runs code that a `Gc` must finalize.
+    fn ty_needs_finalizer(&self, ty: Ty) -> bool;
+
+    /// Check if the type implements `FinalizerSafe`.
+    fn ty_is_finalizer_safe(&self, ty: Ty) -> bool;
+
+    /// Check if the type implements `DropMethodFinalizerElidable`.
+    fn ty_drop_method_finalizer_elidable(&self, ty: Ty) -> bool;
+
     /// Get the layout shape.
     fn layout_shape(&self, id: Layout) -> LayoutShape;
 
@@ -160,6 +160,15 @@ pub fn is_empty_shim(&self) -> bool {
         self.kind == InstanceKind::Shim && with(|cx| cx.is_empty_drop_shim(self.def))
     }
 
+    /// Check whether this instance is a `Gc` constructor which registers a finalizer.
+    ///
+    /// Finalizer safety analysis (FSA) checks the drop methods of the values passed to these
+    /// constructors. This uses the same check as FSA, so constructors such as `Default::default`
+    /// which reach `Gc::new` without being annotated themselves are included.
+    pub fn is_fsa_entry_point(&self) -> bool {
+        with(|cx| cx.is_fsa_entry_point(self.def))
+    }
+
     /// Try to constant evaluate the instance into a constant with the given type.
     ///
     /// This can be used to retrieve a constant that represents an intrinsic return such as
@@ -91,6 +91,30 @@ pub fn unsigned_ty(inner: UintTy) -> Ty {
     pub fn layout(self) -> Result<Layout, Error> {
         with(|cx| cx.ty_layout(self))
     }
+
+    /// Whether this type is `Gc<T>`.
+    pub fn is_gc(self) -> bool {
+        with(|cx| cx.ty_is_gc(self))
+    }
+
+    /// Whether a value of this type needs a finalizer when it is owned by a `Gc`.
+    ///
+    /// This is false if dropping the value is a no-op, or if its drop glue only frees memory
+    /// which the collector would reclaim anyway.
+    pub fn needs_finalizer(self) -> bool {
+        with(|cx| cx.ty_needs_finalizer(self))
+    }
+
+    /// Whether this type implements `FinalizerSafe`, i.e. it may be accessed from a finalizer.
+    pub fn is_finalizer_safe(self) -> bool {
+        with(|cx| cx.ty_is_finalizer_safe(self))
+    }
+
+    /// Whether this type implements `DropMethodFinalizerElidable`, i.e. its drop method does not
+    /// need to run when the value is finalized.
+    pub fn drop_method_finalizer_elidable(self) -> bool {
+        with(|cx| cx.ty_drop_method_finalizer_elidable(self))
+    }
 }
 
 impl Ty {
@@ -65,6 +65,15 @@ pub struct GcCounters {
     #[rustc_nounwind]
     #[cfg_attr(not(bootstrap), rustc_fsa_safe_fn)]
     fn __rust_alloc_zeroed(size: usize, align: usize) -> *mut u8;
+    #[rustc_allocator]
+    #[rustc_nounwind]
+    #[cfg_attr(not(bootstrap), rustc_fsa_safe_fn)]
+    #[cfg(not(bootstrap))]
+    fn __rust_alloc_atomic(size: usize, align: usize) -> *mut u8;
+    #[rustc_nounwind]
+    #[cfg_attr(not(bootstrap), rustc_fsa_safe_fn)]
+    #[cfg(not(bootstrap))]
+    fn __rust_realloc_scanned(ptr: *mut u8, size: usize, align: usize) -> *mut u8;
 
     static __rust_no_alloc_shim_is_unstable: u8;
 }
@@ -205,6 +214,55 @@ pub unsafe fn alloc_zeroed(layout: Layout) -> *mut u8 {
     unsafe { __rust_alloc_zeroed(layout.size(), layout.align()) }
 }
 
+/// Allocate memory which will never hold pointers with the global allocator.
+///
+/// This function forwards calls to the [`GlobalAlloc::alloc_atomic`] method
+/// of the allocator registered with the `#[global_allocator]` attribute
+/// if there is one, or the `std` crate’s default.
+///
+/// # Safety
+///
+/// See [`GlobalAlloc::alloc_atomic`].
+#[unstable(feature = "gc", issue = "none")]
+#[cfg_attr(not(bootstrap), rustc_fsa_safe_fn)]
+#[must_use = "losing the pointer will leak memory"]
+#[inline]
+pub unsafe fn alloc_atomic(layout: Layout) -> *mut u8 {
+    #[cfg(not(bootstrap))]
+    unsafe {
+        __rust_alloc_atomic(layout.size(), layout.align())
+    }
+    #[cfg(bootstrap)]
+    unsafe {
+        alloc(layout)
+    }
+}
+
+/// Make sure that a block of memory may hold pointers with the global allocator.
+///
+/// This function forwards calls to the [`GlobalAlloc::realloc_scanned`] method
+/// of the allocator registered with the `#[global_allocator]` attribute
+/// if there is one, or the `std` crate’s default.
+///
+/// # Safety
+///
+/// See [`GlobalAlloc::realloc_scanned`].
+#[unstable(feature = "gc", issue = "none")]
+#[cfg_attr(not(bootstrap), rustc_fsa_safe_fn)]
+#[must_use = "losing the pointer will leak memory"]
+#[inline]
+pub unsafe fn realloc_scanned(ptr: *mut u8, layout: Layout) -> *mut u8 {
+    #[cfg(not(bootstrap))]
+    unsafe {
+        __rust_realloc_scanned(ptr, layout.size(), layout.align())
+    }
+    #[cfg(bootstrap)]
+    {
+        let _ = layout;
+        ptr
+    }
+}
+
 #[cfg(not(test))]
 impl Global {
     #[inline]
@@ -281,6 +339,33 @@ fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
         self.alloc_impl(layout, true)
     }
 
+    #[inline]
+    unsafe fn allocate_atomic(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        match layout.size() {
+            0 => Ok(NonNull::slice_from_raw_parts(layout.dangling(), 0)),
+            // SAFETY: `layout` is non-zero in size, and the caller promises
+            // not to store pointers in the block.
+            size => unsafe {
+                let ptr = NonNull::new(alloc_atomic(layout)).ok_or(AllocError)?;
+                Ok(NonNull::slice_from_raw_parts(ptr, size))
+            },
+        }
+    }
+
+    #[inline]
+    unsafe fn reallocate_scanned(
+        &self,
+        ptr: NonNull<u8>,
+        layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        match layout.size() {
+            0 => Ok(ptr),
+            // SAFETY: `layout` is non-zero in size,
+            // other conditions must be upheld by the caller
+            _ => unsafe { NonNull::new(realloc_scanned(ptr.as_ptr(), layout)).ok_or(AllocError) },
+        }
+    }
+
     #[inline]
     unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
         if layout.size() != 0 {
@@ -356,8 +441,27 @@ unsafe fn shrink(
     }
 }
 
+#[unstable(feature = "gc", issue = "none")]
+#[cfg(not(test))]
+unsafe impl core::gc::GcScannedAllocator for Global {}
+
+/// Allocates memory which only ever holds values of type `T` from `alloc`,
+/// telling it when `T` can never hold pointers.
+#[inline]
+pub(crate) fn allocate_for<T, A: Allocator + ?Sized>(
+    alloc: &A,
+    layout: Layout,
+) -> Result<core::ptr::NonNull<[u8]>, AllocError> {
+    #[cfg(not(bootstrap))]
+    if !core::mem::may_contain_pointers::<T>() {
+        // SAFETY: the memory only ever holds `T`s, which contain no pointers.
+        return unsafe { alloc.allocate_atomic(layout) };
+    }
+    alloc.allocate(layout)
+}
+
 /// The allocator for unique pointers.
-#[cfg(all(not(no_global_oom_handling), not(test)))]
+#[cfg(all(not(no_global_oom_handling), not(test), bootstrap))]
 #[lang = "exchange_malloc"]
 #[inline]
 unsafe fn exchange_malloc(size: usize, align: usize) -> *mut u8 {
@@ -368,6 +472,24 @@ unsafe fn exchange_malloc(size: usize, align: usize) -> *mut u8 {
     }
 }
 
+/// The allocator for unique pointers. `T` is the type of the boxed value.
+#[cfg(all(not(no_global_oom_handling), not(test), not(bootstrap)))]
+#[lang = "exchange_malloc"]
+#[inline]
+unsafe fn exchange_malloc<T>(size: usize, align: usize) -> *mut u8 {
+    let layout = unsafe { Layout::from_size_align_unchecked(size, align) };
+    let result = if core::mem::may_contain_pointers::<T>() {
+        Global.allocate(layout)
+    } else {
+        // SAFETY: the box only ever holds a `T`, which contains no pointers.
+        unsafe { Global.allocate_atomic(layout) }
+    };
+    match result {
+        Ok(ptr) => ptr.as_mut_ptr(),
+        Err(_) => handle_alloc_error(layout),
+    }
+}
+
 // # Allocation error handler
 
 #[cfg(not(no_global_oom_handling))]
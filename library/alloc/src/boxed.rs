@@ -486,7 +486,7 @@ pub fn try_new_uninit_in(alloc: A) -> Result<Box<mem::MaybeUninit<T>, A>, AllocE
             NonNull::dangling()
         } else {
             let layout = Layout::new::<mem::MaybeUninit<T>>();
-            alloc.allocate(layout)?.cast()
+            crate::alloc::allocate_for::<T, _>(&alloc, layout)?.cast()
         };
         unsafe { Ok(Box::from_raw_in(ptr.as_ptr(), alloc)) }
     }
@@ -693,7 +693,7 @@ pub fn try_new_uninit_slice(len: usize) -> Result<Box<[mem::MaybeUninit<T>]>, Al
                 Ok(l) => l,
                 Err(_) => return Err(AllocError),
             };
-            Global.allocate(layout)?.cast()
+            crate::alloc::allocate_for::<T, _>(&Global, layout)?.cast()
         };
         unsafe { Ok(RawVec::from_raw_parts_in(ptr.as_ptr(), len, Global).into_box(len)) }
     }
@@ -951,6 +951,19 @@ impl<T: ?Sized> Box<T> {
     #[inline]
     #[must_use = "call `drop(Box::from_raw(ptr))` if you intend to drop the `Box`"]
     pub unsafe fn from_raw(raw: *mut T) -> Self {
+        // The memory may have been allocated with `Allocator::allocate_atomic`
+        // for a type which can't hold pointers.
+        #[cfg(not(bootstrap))]
+        let raw = if mem::may_contain_pointers::<T>() {
+            let layout = unsafe { Layout::for_value_raw(raw) };
+            let ptr = unsafe { NonNull::new_unchecked(raw as *mut u8) };
+            match unsafe { Global.reallocate_scanned(ptr, layout) } {
+                Ok(ptr) => ptr.as_ptr().with_metadata_of(raw),
+                Err(_) => handle_alloc_error(layout),
+            }
+        } else {
+            raw
+        };
         unsafe { Self::from_raw_in(raw, Global) }
     }
 }
@@ -144,6 +144,13 @@ impl<T, A: Allocator> RawVec<T, A> {
         1
     };
 
+    /// Whether the buffer can never hold pointers, so the allocator need not
+    /// scan it (see `Allocator::allocate_atomic`).
+    #[cfg(not(bootstrap))]
+    const POINTER_FREE: bool = !mem::may_contain_pointers::<T>();
+    #[cfg(bootstrap)]
+    const POINTER_FREE: bool = false;
+
     /// Like `new`, but parameterized over the choice of allocator for
     /// the returned `RawVec`.
     pub const fn new_in(alloc: A) -> Self {
@@ -228,7 +235,7 @@ fn try_allocate_in(
             }
 
             let result = match init {
-                AllocInit::Uninitialized => alloc.allocate(layout),
+                AllocInit::Uninitialized => crate::alloc::allocate_for::<T, _>(&alloc, layout),
                 #[cfg(not(no_global_oom_handling))]
                 AllocInit::Zeroed => alloc.allocate_zeroed(layout),
             };
@@ -271,6 +278,22 @@ pub(crate) unsafe fn from_nonnull_in(ptr: NonNull<T>, capacity: usize, alloc: A)
         Self { ptr: Unique::from(ptr), cap, alloc }
     }
 
+    /// Makes sure the buffer may hold pointers if `T` may. A buffer passed to
+    /// `from_raw_parts_in` may have been allocated for a type which can't, with
+    /// `Allocator::allocate_atomic`.
+    pub(crate) fn ensure_scanned(&mut self) {
+        if Self::POINTER_FREE {
+            return;
+        }
+        if let Some((ptr, layout)) = self.current_memory() {
+            // SAFETY: `ptr` and `layout` describe the buffer this `RawVec` owns.
+            match unsafe { self.alloc.reallocate_scanned(ptr, layout) } {
+                Ok(ptr) => self.ptr = Unique::from(ptr.cast()),
+                Err(_) => handle_alloc_error(layout),
+            }
+        }
+    }
+
     /// Gets a raw pointer to the start of the allocation. Note that this is
     /// `Unique::dangling()` if `capacity == 0` or `T` is zero-sized. In the former case, you must
     /// be careful.
@@ -483,7 +506,8 @@ fn grow_amortized(&mut self, len: usize, additional: usize) -> Result<(), TryRes
         let new_layout = Layout::array::<T>(cap);
 
         // `finish_grow` is non-generic over `T`.
-        let ptr = finish_grow(new_layout, self.current_memory(), &mut self.alloc)?;
+        let ptr =
+            finish_grow(new_layout, self.current_memory(), &mut self.alloc, Self::POINTER_FREE)?;
         // SAFETY: finish_grow would have resulted in a capacity overflow if we tried to allocate more than isize::MAX items
         unsafe { self.set_ptr_and_cap(ptr, cap) };
         Ok(())
@@ -503,7 +527,8 @@ fn grow_exact(&mut self, len: usize, additional: usize) -> Result<(), TryReserve
         let new_layout = Layout::array::<T>(cap);
 
         // `finish_grow` is non-generic over `T`.
-        let ptr = finish_grow(new_layout, self.current_memory(), &mut self.alloc)?;
+        let ptr =
+            finish_grow(new_layout, self.current_memory(), &mut self.alloc, Self::POINTER_FREE)?;
         // SAFETY: finish_grow would have resulted in a capacity overflow if we tried to allocate more than isize::MAX items
         unsafe {
             self.set_ptr_and_cap(ptr, cap);
@@ -554,6 +579,7 @@ fn finish_grow<A>(
     new_layout: Result<Layout, LayoutError>,
     current_memory: Option<(NonNull<u8>, Layout)>,
     alloc: &mut A,
+    pointer_free: bool,
 ) -> Result<NonNull<[u8]>, TryReserveError>
 where
     A: Allocator,
@@ -570,6 +596,9 @@ fn finish_grow<A>(
             hint::assert_unchecked(old_layout.align() == new_layout.align());
             alloc.grow(ptr, old_layout, new_layout)
         }
+    } else if pointer_free {
+        // SAFETY: the caller promises that the buffer never holds pointers.
+        unsafe { alloc.allocate_atomic(new_layout) }
     } else {
         alloc.allocate(new_layout)
     };
@@ -251,6 +251,7 @@
 use core::cell::Cell;
 use core::cmp::Ordering;
 use core::fmt;
+use core::gc::FinalizerElidableWithGcAllocator;
 use core::hash::{Hash, Hasher};
 use core::hint;
 use core::intrinsics::abort;
@@ -2198,6 +2199,10 @@ fn drop(&mut self) {
     }
 }
 
+// Only for `Global`; see `FinalizerElidableWithGcAllocator`.
+#[unstable(feature = "gc", issue = "none")]
+unsafe impl<T: ?Sized> FinalizerElidableWithGcAllocator for Rc<T> {}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T: ?Sized, A: Allocator + Clone> Clone for Rc<T, A> {
     /// Makes a clone of the `Rc` pointer.
@@ -12,6 +12,7 @@
 use core::borrow;
 use core::cmp::Ordering;
 use core::fmt;
+use core::gc::FinalizerElidableWithGcAllocator;
 use core::hash::{Hash, Hasher};
 use core::hint;
 use core::intrinsics::abort;
@@ -2486,6 +2487,10 @@ fn drop(&mut self) {
     }
 }
 
+// Only for `Global`; see `FinalizerElidableWithGcAllocator`.
+#[unstable(feature = "gc", issue = "none")]
+unsafe impl<T: ?Sized> FinalizerElidableWithGcAllocator for Arc<T> {}
+
 impl<A: Allocator> Arc<dyn Any + Send + Sync, A> {
     /// Attempt to downcast the `Arc<dyn Any + Send + Sync>` to a concrete type.
     ///
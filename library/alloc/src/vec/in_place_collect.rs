@@ -175,6 +175,14 @@ const fn in_place_collectible<DEST, SRC>(
         return false;
     }
 
+    // A buffer for pointer-free elements may come from `Allocator::allocate_atomic`, which a
+    // garbage collector does not scan, so it cannot be reused for elements which may hold
+    // pointers (or vice versa, which would make the later deallocation mismatch).
+    #[cfg(not(bootstrap))]
+    if const { mem::may_contain_pointers::<SRC>() != mem::may_contain_pointers::<DEST>() } {
+        return false;
+    }
+
     match (step_merge, step_expand) {
         (Some(step_merge), Some(step_expand)) => {
             // At least N merged source items -> at most M expanded destination items
@@ -829,7 +829,9 @@ pub fn try_with_capacity_in(capacity: usize, alloc: A) -> Result<Self, TryReserv
     #[inline]
     #[unstable(feature = "allocator_api", issue = "32838")]
     pub unsafe fn from_raw_parts_in(ptr: *mut T, length: usize, capacity: usize, alloc: A) -> Self {
-        unsafe { Vec { buf: RawVec::from_raw_parts_in(ptr, capacity, alloc), len: length } }
+        let mut buf = unsafe { RawVec::from_raw_parts_in(ptr, capacity, alloc) };
+        buf.ensure_scanned();
+        Vec { buf, len: length }
     }
 
     /// A convenience method for hoisting the non-null precondition out of [`Vec::from_raw_parts_in`].
@@ -34,6 +34,10 @@ fn main() {
         .define("enable_parallel_mark", "Off")
         .cflag("-DGC_ALWAYS_MULTITHREADED");
 
+    if env::var_os("CARGO_FEATURE_MANUAL_VDB").is_some() {
+        build.cflag("-DMANUAL_VDB");
+    }
+
     if env::var("ENABLE_GC_ASSERTIONS").map_or(false, |v| v == "true") {
         build.define("enable_gc_assertions", "ON");
     }
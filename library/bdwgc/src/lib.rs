@@ -38,6 +38,22 @@ pub struct ProfileStats {
 
     pub fn GC_malloc(nbytes: usize) -> *mut u8;
 
+    pub fn GC_malloc_atomic(nbytes: usize) -> *mut u8;
+
+    pub fn GC_malloc_many(lb: usize) -> *mut u8;
+
+    pub fn GC_malloc_uncollectable(nbytes: usize) -> *mut u8;
+
+    pub fn GC_generic_malloc(lb: usize, k: i32) -> *mut u8;
+
+    pub fn GC_new_free_list() -> *mut *mut u8;
+
+    pub fn GC_new_kind(free_list: *mut *mut u8, descr: usize, adjust: i32, clear: i32) -> u32;
+
+    pub fn GC_get_kind_and_size(ptr: *const u8, psize: *mut usize) -> i32;
+
+    pub fn GC_size(ptr: *const u8) -> usize;
+
     pub fn GC_posix_memalign(mem_ptr: *mut *mut u8, align: usize, nbytes: usize) -> i32;
 
     pub fn GC_realloc(old: *mut u8, new_size: usize) -> *mut u8;
@@ -62,8 +78,42 @@ pub fn GC_register_finalizer_no_order(
         old_client_data: *mut *mut u8,
     );
 
+    pub fn GC_general_register_disappearing_link(link: *mut *mut u8, obj: *const u8) -> i32;
+
+    pub fn GC_unregister_disappearing_link(link: *mut *mut u8) -> i32;
+
+    pub fn GC_call_with_alloc_lock(
+        f: unsafe extern "C" fn(*mut u8) -> *mut u8,
+        client_data: *mut u8,
+    ) -> *mut u8;
+
+    pub fn GC_is_marked(ptr: *const u8) -> i32;
+
+    pub fn GC_mark_and_push(
+        obj: *mut u8,
+        mark_stack_ptr: *mut u8,
+        mark_stack_limit: *mut u8,
+        src: *mut *mut u8,
+    ) -> *mut u8;
+
+    pub fn GC_set_mark_stack_empty(
+        f: Option<unsafe extern "C" fn(*mut u8, *mut u8) -> *mut u8>,
+    );
+
+    pub fn GC_get_mark_stack_empty() -> Option<unsafe extern "C" fn(*mut u8, *mut u8) -> *mut u8>;
+
     pub fn GC_gcollect();
 
+    pub fn GC_set_manual_vdb_allowed(value: i32);
+
+    pub fn GC_enable_incremental();
+
+    pub fn GC_is_incremental_mode() -> i32;
+
+    pub fn GC_end_stubborn_change(ptr: *const u8);
+
+    pub fn GC_get_hblk_size() -> usize;
+
     pub fn GC_thread_is_registered() -> u32;
 
     pub fn GC_pthread_create(
@@ -200,6 +200,56 @@ unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
         ptr
     }
 
+    /// Behaves like `alloc`, but tells the allocator that the block will
+    /// never hold pointers.
+    ///
+    /// A garbage collecting allocator can use this to avoid scanning the
+    /// block for pointers, which saves marking time and avoids retaining
+    /// objects whose addresses happen to appear in the data. The default
+    /// implementation ignores the hint and calls `alloc`.
+    ///
+    /// `Vec` and `Box` call this (through [`Allocator::allocate_atomic`]) for
+    /// element types which contain no pointers; see
+    /// [`mem::may_contain_pointers`].
+    ///
+    /// # Safety
+    ///
+    /// This function is unsafe for the same reasons that `alloc` is. In
+    /// addition, the caller must never store a pointer (or an integer which
+    /// is later turned back into a pointer) in the allocated block, nor grow
+    /// the block with `realloc` into one which does, unless it has first
+    /// been passed to [`realloc_scanned`].
+    ///
+    /// [`Allocator::allocate_atomic`]: crate::alloc::Allocator::allocate_atomic
+    /// [`mem::may_contain_pointers`]: crate::mem::may_contain_pointers
+    /// [`realloc_scanned`]: GlobalAlloc::realloc_scanned
+    #[unstable(feature = "gc", issue = "none")]
+    unsafe fn alloc_atomic(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: the safety contract for `alloc` must be upheld by the caller.
+        unsafe { self.alloc(layout) }
+    }
+
+    /// Makes sure that the block at `ptr` may hold pointers, moving it if it
+    /// came from [`alloc_atomic`].
+    ///
+    /// `Vec::from_raw_parts` and `Box::from_raw` call this when their element
+    /// type may hold pointers, since the block may have been allocated for a
+    /// type which can't. The default implementation returns `ptr` unchanged,
+    /// which is correct for any allocator whose `alloc_atomic` is `alloc`.
+    ///
+    /// # Safety
+    ///
+    /// The same as for `realloc` with `new_size` equal to `layout.size()`.
+    /// If this returns a non-null pointer, ownership of the block has moved
+    /// to it. If it returns null, the block at `ptr` is left untouched.
+    ///
+    /// [`alloc_atomic`]: GlobalAlloc::alloc_atomic
+    #[unstable(feature = "gc", issue = "none")]
+    unsafe fn realloc_scanned(&self, ptr: *mut u8, layout: Layout) -> *mut u8 {
+        let _ = layout;
+        ptr
+    }
+
     /// Shrink or grow a block of memory to the given `new_size` in bytes.
     /// The block is described by the given `ptr` pointer and `layout`.
     ///
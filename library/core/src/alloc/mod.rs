@@ -108,6 +108,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 ///
 /// [*currently allocated*]: #currently-allocated-memory
 #[unstable(feature = "allocator_api", issue = "32838")]
+#[rustc_diagnostic_item = "Allocator"]
 pub unsafe trait Allocator {
     /// Attempts to allocate a block of memory.
     ///
@@ -157,6 +158,56 @@ fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
         Ok(ptr)
     }
 
+    /// Behaves like `allocate`, but tells the allocator that the returned memory will never hold
+    /// pointers.
+    ///
+    /// A garbage collecting allocator can use this to avoid scanning the block for pointers. The
+    /// default implementation ignores the hint and calls `allocate`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must never store a pointer (or an integer which is later turned back into a
+    /// pointer) in the returned block, nor in any block it is grown into.
+    ///
+    /// # Errors
+    ///
+    /// Returning `Err` indicates that either memory is exhausted or `layout` does not meet
+    /// allocator's size or alignment constraints, just as in `allocate`.
+    #[unstable(feature = "gc", issue = "none")]
+    unsafe fn allocate_atomic(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.allocate(layout)
+    }
+
+    /// Makes sure that the memory block at `ptr` may hold pointers, moving it if it came from
+    /// [`allocate_atomic`](Allocator::allocate_atomic).
+    ///
+    /// The default implementation returns `ptr` unchanged.
+    ///
+    /// # Safety
+    ///
+    /// * `ptr` must denote a block of memory [*currently allocated*] via this allocator, and
+    /// * `layout` must [*fit*] that block of memory.
+    ///
+    /// If this returns `Ok`, ownership of the block has moved to the returned pointer, just as for
+    /// `grow`.
+    ///
+    /// # Errors
+    ///
+    /// Returning `Err` indicates that the block had to be moved and memory is exhausted. The block
+    /// at `ptr` is left untouched.
+    ///
+    /// [*currently allocated*]: #currently-allocated-memory
+    /// [*fit*]: #memory-fitting
+    #[unstable(feature = "gc", issue = "none")]
+    unsafe fn reallocate_scanned(
+        &self,
+        ptr: NonNull<u8>,
+        layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        let _ = layout;
+        Ok(ptr)
+    }
+
     /// Deallocates the memory referenced by `ptr`.
     ///
     /// # Safety
@@ -386,6 +437,22 @@ fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
         (**self).allocate_zeroed(layout)
     }
 
+    #[inline]
+    unsafe fn allocate_atomic(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        // SAFETY: the safety contract must be upheld by the caller
+        unsafe { (**self).allocate_atomic(layout) }
+    }
+
+    #[inline]
+    unsafe fn reallocate_scanned(
+        &self,
+        ptr: NonNull<u8>,
+        layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        // SAFETY: the safety contract must be upheld by the caller
+        unsafe { (**self).reallocate_scanned(ptr, layout) }
+    }
+
     #[inline]
     unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
         // SAFETY: the safety contract must be upheld by the caller
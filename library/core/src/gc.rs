@@ -2,9 +2,53 @@
 #![allow(missing_docs)]
 use crate::ops::{Deref, DerefMut};
 
+#[cfg(not(bootstrap))]
+extern "Rust" {
+    /// Defined in std. Tells the collector that the `len` bytes at `dst` have
+    /// just been written to.
+    #[rustc_nounwind]
+    fn __rust_gc_write_barrier(dst: *const u8, len: usize);
+}
+
+/// Tells the collector that `count` values of type `T` have just been written
+/// to `dst`.
+///
+/// When rustc is built with write barriers, it calls this after every store
+/// through a pointer, so that an incremental collection can find the pages
+/// which changed since it last scanned them. Stores of types which cannot
+/// hold a pointer compile to nothing. Const evaluation has no collector to
+/// tell, so there this does nothing at all.
+///
+/// Binaries compiled with write barriers must link std, which provides the
+/// other half of the barrier.
+#[cfg(not(bootstrap))]
+#[lang = "gc_write_barrier"]
+#[rustc_const_unstable(feature = "gc", issue = "none")]
+#[inline(always)]
+#[rustc_nounwind]
+pub const unsafe fn write_barrier<T>(dst: *const T, count: usize) {
+    const fn comptime(_: *const u8, _: usize) {}
+
+    #[inline]
+    fn runtime(dst: *const u8, len: usize) {
+        // SAFETY: the barrier only records which pages `dst..dst + len`
+        // covers, it never dereferences `dst`.
+        unsafe { __rust_gc_write_barrier(dst, len) }
+    }
+
+    if crate::intrinsics::may_contain_pointers::<T>() {
+        let len = count.wrapping_mul(crate::mem::size_of::<T>());
+        crate::intrinsics::const_eval_select((dst as *const u8, len), comptime, runtime);
+    }
+}
+
 /// Prevents a type from being finalized by GC if none of the component types
 /// need dropping.
 ///
+/// The compiler infers this for any type whose drop method deallocates memory
+/// and otherwise only drops the values it owns, so most containers don't need
+/// to implement it by hand.
+///
 /// # Safety
 ///
 /// Unsafe because this should be used with care. Preventing drop from
@@ -13,6 +57,51 @@
 #[cfg_attr(not(bootstrap), lang = "drop_method_finalizer_elidable")]
 pub unsafe trait DropMethodFinalizerElidable {}
 
+/// Like `DropMethodFinalizerElidable`, for drop methods which are only
+/// redundant when `GcAllocator` is the global allocator.
+///
+/// This is intended for reference counted pointers such as `Rc` and `Arc`.
+/// When their backing allocation comes from the GC heap, the reference count
+/// traffic in their drop methods is redundant inside a finalizer: the
+/// collector frees the allocation once it is unreachable, and the pointee is
+/// still finalized if it needs to be.
+///
+/// `mem::needs_finalizer` does not honour this, as the crate being compiled
+/// may not be able to see which global allocator the program uses. Instead,
+/// `Gc::new` checks at run-time whether `GcAllocator` is the global allocator
+/// and only skips registering the finalizer if it is, so nothing is leaked
+/// with any other global allocator. Finalizer safety analysis skips such
+/// values only in crates which can already see `GcAllocator`.
+///
+/// # Safety
+///
+/// The drop method must do nothing except maintain bookkeeping for memory
+/// allocated through the global allocator, drop the type's components and
+/// deallocate. Skipping it can leave the bookkeeping stale, so any
+/// uniqueness checks based on it must fail conservatively (e.g. `get_mut`
+/// returning `None`).
+#[cfg_attr(not(bootstrap), lang = "finalizer_elidable_with_gc_allocator")]
+pub unsafe trait FinalizerElidableWithGcAllocator {}
+
+/// Marks allocators whose memory the collector scans for pointers to GC
+/// objects.
+///
+/// A `Gc` stored in memory from any other allocator (e.g. `System`) is hidden
+/// from the collector, so the `gc_in_unscanned_memory` lint warns about
+/// containers holding a `Gc` which use such an allocator.
+///
+/// `Global` implements this trait: whether the global allocator is actually
+/// `GcAllocator` is checked separately by the `gc_without_gc_allocator` lint.
+///
+/// # Safety
+///
+/// Every live allocation made through the allocator must be scanned
+/// conservatively by the collector.
+#[cfg_attr(not(bootstrap), lang = "gc_scanned_allocator")]
+pub unsafe trait GcScannedAllocator {}
+
+unsafe impl<A: GcScannedAllocator + ?Sized> GcScannedAllocator for &A {}
+
 /// A wrapper which prevents `T` from being finalized when used in a `Gc`.
 ///
 /// This is useful for when its not possible to implement `DropMethodFinalizerElidable`
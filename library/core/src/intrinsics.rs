@@ -1481,6 +1481,43 @@ pub const fn unlikely(b: bool) -> bool {
     #[cfg(not(bootstrap))]
     pub fn needs_finalizer<T>() -> bool;
 
+    /// Like `needs_finalizer`, but returns `false` for types whose drop
+    /// methods are only redundant when `GcAllocator` is the global allocator
+    /// (see [`FinalizerElidableWithGcAllocator`](crate::gc::FinalizerElidableWithGcAllocator)).
+    /// Callers must check the global allocator themselves.
+    #[rustc_const_unstable(feature = "gc", issue = "none")]
+    #[rustc_safe_intrinsic]
+    #[rustc_nounwind]
+    #[cfg(not(bootstrap))]
+    pub fn needs_finalizer_with_gc_allocator<T>() -> bool;
+
+    /// Returns `false` if values of type `T` can never hold a pointer, so
+    /// memory holding only `T`s need not be scanned by a garbage collector.
+    ///
+    /// The public form of this intrinsic is
+    /// [`mem::may_contain_pointers`](crate::mem::may_contain_pointers).
+    #[rustc_const_unstable(feature = "gc", issue = "none")]
+    #[rustc_safe_intrinsic]
+    #[rustc_nounwind]
+    #[cfg(not(bootstrap))]
+    pub fn may_contain_pointers<T: ?Sized>() -> bool;
+
+    /// Keeps the object that `ptr` points to alive for the collector until
+    /// this point in the program.
+    ///
+    /// This is lowered to an empty inline assembly block which uses `ptr` as a
+    /// register operand. It has no runtime cost, but the optimizer can't
+    /// prove that `ptr` is dead beforehand, so a conservative GC will still
+    /// find it on the stack or in a register while the object is in use.
+    ///
+    /// See [`std::gc::keep_alive`] for the public interface.
+    ///
+    /// [`std::gc::keep_alive`]: ../../std/gc/fn.keep_alive.html
+    #[rustc_safe_intrinsic]
+    #[rustc_nounwind]
+    #[cfg(not(bootstrap))]
+    pub fn gc_keep_alive(ptr: *const u8);
+
     /// Calculates the offset from a pointer.
     ///
     /// This is implemented as an intrinsic to avoid converting to and from an
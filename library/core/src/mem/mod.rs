@@ -617,6 +617,40 @@ pub const fn needs_finalizer<T>() -> bool {
     intrinsics::needs_finalizer::<T>()
 }
 
+/// Returns `false` if values of type `T` can never hold a pointer, or `true`
+/// otherwise.
+///
+/// A type can hold a pointer if any of its fields is a reference, raw or
+/// function pointer, trait object, union (including [`MaybeUninit`]), or an
+/// integer at least as wide as a pointer, recursively. Narrower integers,
+/// floats, `bool`, `char` and `str` are assumed never to hold pointers.
+///
+/// Like [`needs_drop`], this may be conservative: it may return `true` for
+/// types which never hold pointers. If it returns `false`, then allocations
+/// holding only `T`s can be made with [`GlobalAlloc::alloc_atomic`], which
+/// the garbage collector does not scan.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(gc)]
+/// use std::mem;
+///
+/// assert!(!mem::may_contain_pointers::<[u8; 16]>());
+/// assert!(!mem::may_contain_pointers::<(f64, char)>());
+/// assert!(mem::may_contain_pointers::<&u8>());
+/// assert!(mem::may_contain_pointers::<usize>());
+/// ```
+///
+/// [`GlobalAlloc::alloc_atomic`]: crate::alloc::GlobalAlloc::alloc_atomic
+#[inline]
+#[unstable(feature = "gc", issue = "none")]
+#[rustc_const_unstable(feature = "gc", issue = "none")]
+#[cfg(not(bootstrap))]
+pub const fn may_contain_pointers<T: ?Sized>() -> bool {
+    intrinsics::may_contain_pointers::<T>()
+}
+
 /// Returns the value of type `T` represented by the all-zero byte-pattern.
 ///
 /// This means that, for example, the padding byte in `(u8, u16)` is not
@@ -0,0 +1,160 @@
+//! Microbenchmarks for `GcAllocator` and `Gc`.
+//!
+//! Objects of up to 64 bytes are served from per-thread free lists, while
+//! larger ones always go through `GC_malloc`. The `*_gc_malloc` benchmarks
+//! call `GC_malloc` directly for the same sizes, which is what `GcAllocator`
+//! did before the free lists, and the `*_slow_path` benchmarks give the cost
+//! of larger allocations for comparison.
+//!
+//! For an end-to-end comparison, run these with and without
+//! `alloy.gc-thread-cache` in `benchmark.config.toml`: with it disabled, every
+//! allocation, including those made by `Gc::new`, goes through the collector.
+//!
+//! The `keep_alive_*` benchmarks compare the `gc_keep_alive` intrinsic, which
+//! `Gc`'s drop uses as its premature finalization barrier, with the call to
+//! `GC_keep_alive` it replaced. Building with
+//! `alloy.premature-finalizer-prevention-ffi` in `benchmark.config.toml` makes
+//! `Gc`'s drop use the call again, so `gc_drop_barrier` gives the end-to-end
+//! difference between the two builds.
+#![cfg(test)]
+
+use std::alloc::{Allocator, Layout};
+use std::gc::{Gc, GcAllocator};
+use std::hint::black_box;
+use test::Bencher;
+
+extern "C" {
+    fn GC_malloc(nbytes: usize) -> *mut u8;
+    fn GC_keep_alive(ptr: *mut u8);
+}
+
+fn bench_alloc(b: &mut Bencher, size: usize) {
+    let layout = Layout::from_size_align(size, 8).unwrap();
+    b.iter(|| {
+        for _ in 0..1000 {
+            black_box(GcAllocator.allocate(layout).unwrap());
+        }
+    })
+}
+
+#[bench]
+fn alloc_16(b: &mut Bencher) {
+    bench_alloc(b, 16)
+}
+
+#[bench]
+fn alloc_32(b: &mut Bencher) {
+    bench_alloc(b, 32)
+}
+
+#[bench]
+fn alloc_64(b: &mut Bencher) {
+    bench_alloc(b, 64)
+}
+
+fn bench_gc_malloc(b: &mut Bencher, size: usize) {
+    b.iter(|| {
+        for _ in 0..1000 {
+            black_box(unsafe { GC_malloc(size) });
+        }
+    })
+}
+
+#[bench]
+fn alloc_16_gc_malloc(b: &mut Bencher) {
+    bench_gc_malloc(b, 16)
+}
+
+#[bench]
+fn alloc_32_gc_malloc(b: &mut Bencher) {
+    bench_gc_malloc(b, 32)
+}
+
+#[bench]
+fn alloc_64_gc_malloc(b: &mut Bencher) {
+    bench_gc_malloc(b, 64)
+}
+
+#[bench]
+fn alloc_80_slow_path(b: &mut Bencher) {
+    bench_alloc(b, 80)
+}
+
+#[bench]
+fn alloc_128_slow_path(b: &mut Bencher) {
+    bench_alloc(b, 128)
+}
+
+#[allow(dead_code)]
+struct Node {
+    left: Option<Gc<Node>>,
+    right: Option<Gc<Node>>,
+    value: u64,
+}
+
+#[bench]
+fn gc_new_node(b: &mut Bencher) {
+    b.iter(|| {
+        let mut tree = None;
+        for value in 0..1000 {
+            tree = Some(Gc::new(Node { left: tree, right: None, value }));
+        }
+        black_box(tree)
+    })
+}
+
+#[bench]
+fn gc_new_node_slow_path(b: &mut Bencher) {
+    b.iter(|| {
+        let mut tree = None;
+        for value in 0..1000 {
+            // Padding the node past 64 bytes sends it through `GC_malloc`.
+            tree = Some(Gc::new((Node { left: tree, right: None, value }, [0u64; 6])));
+        }
+        black_box(tree)
+    })
+}
+
+fn gcs() -> Vec<Gc<u64>> {
+    (0..1000).map(Gc::new).collect()
+}
+
+#[bench]
+fn keep_alive_intrinsic(b: &mut Bencher) {
+    let gcs = gcs();
+    b.iter(|| {
+        let mut sum = 0;
+        for gc in &gcs {
+            sum += **gc;
+            std::intrinsics::gc_keep_alive(Gc::as_ptr(gc) as *const u8);
+        }
+        sum
+    })
+}
+
+#[bench]
+fn keep_alive_ffi(b: &mut Bencher) {
+    let gcs = gcs();
+    b.iter(|| {
+        let mut sum = 0;
+        for gc in &gcs {
+            sum += **gc;
+            unsafe { GC_keep_alive(Gc::as_ptr(gc) as *mut u8) };
+        }
+        sum
+    })
+}
+
+#[bench]
+fn gc_drop_barrier(b: &mut Bencher) {
+    let gcs = gcs();
+    b.iter(|| {
+        let mut sum = 0;
+        for gc in &gcs {
+            // Each copy is dropped at the end of the iteration, which runs the barrier.
+            let gc = black_box(*gc);
+            sum += *gc;
+        }
+        sum
+    })
+}
@@ -1,7 +1,11 @@
 // Disabling in Miri as these would take too long.
 #![cfg(not(miri))]
+#![feature(allocator_api)]
+#![feature(core_intrinsics)]
+#![feature(gc)]
 #![feature(test)]
 
 extern crate test;
 
+mod gc;
 mod hash;
@@ -426,4 +426,30 @@ pub mod __default_lib_allocator {
             System.alloc_zeroed(layout)
         }
     }
+
+    #[rustc_std_internal_symbol]
+    #[cfg(not(bootstrap))]
+    pub unsafe extern "C" fn __rdl_alloc_atomic(size: usize, align: usize) -> *mut u8 {
+        // SAFETY: see the guarantees expected by `Layout::from_size_align` and
+        // `GlobalAlloc::alloc_atomic`.
+        unsafe {
+            let layout = Layout::from_size_align_unchecked(size, align);
+            System.alloc_atomic(layout)
+        }
+    }
+
+    #[rustc_std_internal_symbol]
+    #[cfg(not(bootstrap))]
+    pub unsafe extern "C" fn __rdl_realloc_scanned(
+        ptr: *mut u8,
+        size: usize,
+        align: usize,
+    ) -> *mut u8 {
+        // SAFETY: see the guarantees expected by `Layout::from_size_align` and
+        // `GlobalAlloc::realloc_scanned`.
+        unsafe {
+            let layout = Layout::from_size_align_unchecked(size, align);
+            System.realloc_scanned(ptr, layout)
+        }
+    }
 }
@@ -23,6 +23,12 @@
 //! If the `T` in a [`Gc`] has a [`Drop`] method, it will be run using a
 //! finalizer before being deallocated.
 //!
+//! Thread stacks are always scanned conservatively: any word which looks like
+//! a pointer into the GC heap keeps its target alive, and objects never move.
+//! Precise stack maps (e.g. through LLVM statepoints) are not supported, as
+//! BDWGC scans each stack as a whole and offers no way to skip the frames a
+//! stack map would describe, so they could only add to the conservative roots.
+//!
 //! `Gc<T>` automatically dereferences to `T` (via the [`Deref`] trait), so you
 //! can call `T`'s methods on a value of type [`Gc<T>`][`Gc`]. To avoid name
 //! clashes with `T`'s methods, the methods of [`Gc<T>`][`Gc`] itself are
@@ -46,12 +52,23 @@
     marker::Unsize,
     mem::{self, align_of_val_raw, size_of_val, MaybeUninit},
     ops::{CoerceUnsized, Deref, DispatchFromDyn, Receiver},
+    pin::Pin,
     ptr::{self, drop_in_place, NonNull},
+    task::{RawWaker, RawWakerVTable, Waker},
 };
 
 #[cfg(not(no_global_oom_handling))]
 use crate::alloc::{handle_alloc_error, Global};
 #[cfg(not(no_global_oom_handling))]
+use crate::borrow::Cow;
+#[cfg(not(no_global_oom_handling))]
+use crate::ffi::{CStr, CString, OsStr, OsString};
+#[cfg(not(no_global_oom_handling))]
+use crate::path::{Path, PathBuf};
+use crate::sync::{Mutex, PoisonError};
+#[cfg(not(no_global_oom_handling))]
+use core::iter::TrustedLen;
+#[cfg(not(no_global_oom_handling))]
 use core::slice::from_raw_parts_mut;
 
 pub use core::gc::*;
@@ -62,6 +79,15 @@
 #[cfg(feature = "log-stats")]
 use crate::alloc::GC_COUNTERS;
 
+mod ephemeron;
+#[unstable(feature = "gc", issue = "none")]
+pub use self::ephemeron::EphemeronMap;
+mod heap;
+#[unstable(feature = "gc", issue = "none")]
+pub use self::heap::{GcHeap, GcHeapStats, HeapGc};
+#[cfg(all(target_thread_local, not(miri), not(feature = "gc-no-thread-cache")))]
+pub(crate) mod thread_cache;
+
 #[cfg(test)]
 mod tests;
 
@@ -73,6 +99,7 @@
 pub const MIN_ALIGN: usize = 8;
 
 #[derive(Debug)]
+#[cfg_attr(not(test), rustc_diagnostic_item = "GcAllocator")]
 pub struct GcAllocator;
 
 unsafe impl GlobalAlloc for GcAllocator {
@@ -90,11 +117,28 @@ unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
     unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
         unsafe { gc_realloc(ptr, layout, new_size) }
     }
+
+    #[inline]
+    unsafe fn alloc_atomic(&self, layout: Layout) -> *mut u8 {
+        unsafe { gc_malloc_atomic(layout) }
+    }
+
+    #[inline]
+    unsafe fn realloc_scanned(&self, ptr: *mut u8, layout: Layout) -> *mut u8 {
+        unsafe { gc_realloc_scanned(ptr, layout) }
+    }
 }
 
 #[inline]
 unsafe fn gc_malloc(layout: Layout) -> *mut u8 {
     if layout.align() <= MIN_ALIGN && layout.align() <= layout.size() {
+        #[cfg(all(target_thread_local, not(miri), not(feature = "gc-no-thread-cache")))]
+        if layout.size() <= thread_cache::MAX_SIZE {
+            let ptr = unsafe { thread_cache::alloc(layout.size()) };
+            if !ptr.is_null() {
+                return ptr;
+            }
+        }
         unsafe { bdwgc::GC_malloc(layout.size()) as *mut u8 }
     } else {
         let mut out = ptr::null_mut();
@@ -108,6 +152,38 @@ unsafe fn gc_malloc(layout: Layout) -> *mut u8 {
     }
 }
 
+/// Allocates memory which the collector will not scan for pointers.
+#[inline]
+unsafe fn gc_malloc_atomic(layout: Layout) -> *mut u8 {
+    if layout.align() <= MIN_ALIGN && layout.align() <= layout.size() {
+        unsafe { bdwgc::GC_malloc_atomic(layout.size()) as *mut u8 }
+    } else {
+        // There is no aligned variant of `GC_malloc_atomic`, so fall back to
+        // (scanned) aligned memory.
+        unsafe { gc_malloc(layout) }
+    }
+}
+
+/// The BDWGC object kind of blocks from `GC_malloc_atomic`.
+const PTRFREE: i32 = 0;
+
+/// Moves a block from `GC_malloc_atomic` into memory which the collector scans.
+/// BDWGC can't change the kind of an object in place, and `GC_realloc` keeps it.
+#[inline]
+unsafe fn gc_realloc_scanned(ptr: *mut u8, layout: Layout) -> *mut u8 {
+    if unsafe { bdwgc::GC_get_kind_and_size(ptr, ptr::null_mut()) } != PTRFREE {
+        return ptr;
+    }
+    unsafe {
+        let new_ptr = gc_malloc(layout);
+        if !new_ptr.is_null() {
+            ptr::copy_nonoverlapping(ptr, new_ptr, layout.size());
+            gc_free(ptr, layout);
+        }
+        new_ptr
+    }
+}
+
 #[inline]
 unsafe fn gc_realloc(ptr: *mut u8, old_layout: Layout, new_size: usize) -> *mut u8 {
     if old_layout.align() <= MIN_ALIGN && old_layout.align() <= new_size {
@@ -134,6 +210,8 @@ unsafe fn gc_free(ptr: *mut u8, _: Layout) {
     }
 }
 
+unsafe impl GcScannedAllocator for GcAllocator {}
+
 unsafe impl Allocator for GcAllocator {
     #[inline]
     fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
@@ -149,6 +227,32 @@ fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
         }
     }
 
+    #[inline]
+    unsafe fn allocate_atomic(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        #[cfg(feature = "log-stats")]
+        GC_COUNTERS.allocated_gc.fetch_add(1, atomic::Ordering::Relaxed);
+        match layout.size() {
+            0 => Ok(NonNull::slice_from_raw_parts(layout.dangling(), 0)),
+            size => unsafe {
+                let ptr = gc_malloc_atomic(layout);
+                let ptr = NonNull::new(ptr).ok_or(AllocError)?;
+                Ok(NonNull::slice_from_raw_parts(ptr, size))
+            },
+        }
+    }
+
+    #[inline]
+    unsafe fn reallocate_scanned(
+        &self,
+        ptr: NonNull<u8>,
+        layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        match layout.size() {
+            0 => Ok(ptr),
+            _ => unsafe { NonNull::new(gc_realloc_scanned(ptr.as_ptr(), layout)).ok_or(AllocError) },
+        }
+    }
+
     unsafe fn deallocate(&self, _: NonNull<u8>, _: Layout) {}
 }
 
@@ -199,6 +303,28 @@ pub fn init() {
     unsafe { bdwgc::GC_init() }
 }
 
+/// Runs `f` while holding the collector's allocation lock, so that no
+/// collection can see a list of roots half-updated. `f` must not allocate.
+fn with_alloc_lock<F: FnOnce()>(f: F) {
+    unsafe extern "C" fn call<F: FnOnce()>(data: *mut u8) -> *mut u8 {
+        let f = unsafe { (*(data as *mut Option<F>)).take().unwrap_unchecked() };
+        f();
+        ptr::null_mut()
+    }
+    let mut f = Some(f);
+    unsafe { bdwgc::GC_call_with_alloc_lock(call::<F>, &mut f as *mut Option<F> as *mut u8) };
+}
+
+/// Disguises `ptr` so that a conservative scan does not mistake it for a
+/// pointer.
+fn hide<T: ?Sized>(ptr: *const T) -> usize {
+    !(ptr as *const u8 as usize)
+}
+
+fn reveal(hidden: usize) -> *mut u8 {
+    !hidden as *mut u8
+}
+
 pub fn suppress_warnings() {
     unsafe { bdwgc::GC_set_warn_proc(&bdwgc::GC_ignore_warn_proc as *const _ as *mut u8) };
 }
@@ -207,8 +333,86 @@ pub fn thread_registered() -> bool {
     unsafe { bdwgc::GC_thread_is_registered() != 0 }
 }
 
-pub fn keep_alive<T>(ptr: *mut T) {
-    unsafe { bdwgc::GC_keep_alive(ptr as *mut u8) }
+/// Whether the collector is running incrementally, in which case it has to be
+/// told about every write to the heap.
+#[cfg(feature = "gc-write-barriers")]
+static INCREMENTAL: crate::sync::atomic::AtomicBool = crate::sync::atomic::AtomicBool::new(false);
+
+/// The granularity at which the collector tracks dirty memory, as configured
+/// when bdwgc was built. Set before `INCREMENTAL`, so it is never read as 0.
+#[cfg(feature = "gc-write-barriers")]
+static HBLKSIZE: crate::sync::atomic::AtomicUsize = crate::sync::atomic::AtomicUsize::new(0);
+
+/// Switches the collector to incremental collection, where the mark phase is
+/// done a little at a time between allocations rather than while the whole
+/// program is stopped.
+///
+/// Returns `true` if the collector is now running incrementally, which is
+/// only possible if std was built with write barriers (the `gc-write-barriers`
+/// Alloy option). The collector then finds out which memory was written to
+/// during a collection from the barriers the compiler inserts after each store
+/// through a pointer, rather than by write-protecting the heap.
+///
+/// # Safety
+///
+/// Every store of a pointer into the GC heap must be followed by a write
+/// barrier, or the collector can free objects which are still reachable. The
+/// whole program, including any foreign code which writes pointers into GC
+/// memory, must therefore have been compiled with write barriers.
+#[unstable(feature = "gc", issue = "none")]
+pub unsafe fn enable_incremental() -> bool {
+    #[cfg(feature = "gc-write-barriers")]
+    {
+        let hblksize = unsafe { bdwgc::GC_get_hblk_size() };
+        HBLKSIZE.store(hblksize, crate::sync::atomic::Ordering::Relaxed);
+        // Barriers have to be recorded before the first increment starts.
+        INCREMENTAL.store(true, crate::sync::atomic::Ordering::SeqCst);
+        unsafe {
+            bdwgc::GC_set_manual_vdb_allowed(1);
+            bdwgc::GC_enable_incremental();
+        }
+        let incremental = unsafe { bdwgc::GC_is_incremental_mode() } != 0;
+        if !incremental {
+            INCREMENTAL.store(false, crate::sync::atomic::Ordering::SeqCst);
+        }
+        incremental
+    }
+    #[cfg(not(feature = "gc-write-barriers"))]
+    false
+}
+
+/// The runtime half of `core::gc::write_barrier`: marks the pages covering
+/// `dst..dst + len` as dirty, so that the current collection rescans them.
+#[cfg(not(test))]
+#[rustc_std_internal_symbol]
+extern "Rust" fn __rust_gc_write_barrier(dst: *const u8, len: usize) {
+    #[cfg(feature = "gc-write-barriers")]
+    if INCREMENTAL.load(crate::sync::atomic::Ordering::Acquire) && len != 0 {
+        let hblksize = HBLKSIZE.load(crate::sync::atomic::Ordering::Relaxed);
+        unsafe { bdwgc::GC_end_stubborn_change(dst) };
+        let end = dst.addr() + len;
+        let mut page = (dst.addr() | (hblksize - 1)) + 1;
+        while page < end {
+            unsafe { bdwgc::GC_end_stubborn_change(dst.wrapping_byte_add(page - dst.addr())) };
+            page += hblksize;
+        }
+    }
+    #[cfg(not(feature = "gc-write-barriers"))]
+    let _ = (dst, len);
+}
+
+/// Ensures that the object `ptr` points to is not collected (or finalized)
+/// before this point in the program.
+#[inline(always)]
+#[rustc_nounwind]
+#[cfg_attr(not(test), rustc_diagnostic_item = "gc_keep_alive")]
+pub fn keep_alive<T: ?Sized>(ptr: *mut T) {
+    #[cfg(any(bootstrap, feature = "premature-finalizer-prevention-ffi"))]
+    unsafe {
+        bdwgc::GC_keep_alive(ptr as *mut u8)
+    }
+    #[cfg(not(any(bootstrap, feature = "premature-finalizer-prevention-ffi")))]
+    crate::intrinsics::gc_keep_alive(ptr as *const u8);
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -274,8 +478,9 @@ impl<T: ?Sized + Unsize<U>, U: ?Sized> DispatchFromDyn<Gc<U>> for Gc<T> {}
 /// traits to be implemented on a type simultaneously.
 #[cfg(all(not(bootstrap), not(test), feature = "premature-finalizer-prevention"))]
 impl<T: ?Sized> Drop for Gc<T> {
+    #[inline(always)]
     fn drop(&mut self) {
-        keep_alive(self);
+        keep_alive(self.ptr.as_ptr());
     }
 }
 
@@ -411,6 +616,71 @@ pub fn as_ptr(this: &Self) -> *const T {
     pub fn ptr_eq(this: &Self, other: &Self) -> bool {
         crate::ptr::addr_eq(this.ptr.as_ptr(), other.ptr.as_ptr())
     }
+
+    /// Immediately reclaims the allocation `this` points to, instead of
+    /// waiting for the collector to find it unreachable.
+    ///
+    /// If a finalizer was registered for the value, it is unregistered and run
+    /// on the calling thread before the memory is freed. The freed bytes are
+    /// counted in the collector's `expl_freed_bytes_since_gc` statistic.
+    ///
+    /// When std is built with the `gc-poison-freed` feature, the memory is
+    /// instead overwritten with a poison pattern and left for the collector to
+    /// reclaim, so that a use of a dangling `Gc` reads garbage rather than
+    /// another live object. The memory is then never handed to `GC_free`, so
+    /// it does not count towards `expl_freed_bytes_since_gc`.
+    ///
+    /// # Safety
+    ///
+    /// No other `Gc`, reference, or raw pointer to the value may be used after
+    /// this call. Because the collector is conservative, this includes copies
+    /// of `this` which are merely still reachable, such as those stored in
+    /// other objects.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![feature(gc)]
+    /// use std::gc::Gc;
+    ///
+    /// let scratch = Gc::new([0u8; 1024]);
+    /// assert_eq!(scratch[0], 0);
+    /// unsafe { Gc::free(scratch) };
+    /// ```
+    #[unstable(feature = "gc", issue = "none")]
+    pub unsafe fn free(this: Self) {
+        let layout = Layout::for_value(unsafe { this.ptr.as_ref() });
+        if layout.size() == 0 {
+            // Zero-sized values are never allocated by the collector, so no
+            // finalizer can have been registered for them.
+            return;
+        }
+        let obj = this.ptr.as_ptr() as *mut u8;
+
+        let mut finalizer: Option<unsafe extern "C" fn(*mut u8, *mut u8)> = None;
+        let mut client_data = ptr::null_mut();
+        unsafe {
+            bdwgc::GC_register_finalizer_no_order(
+                obj,
+                None,
+                ptr::null_mut(),
+                &mut finalizer as *mut _ as *mut extern "C" fn(*mut u8, *mut u8),
+                &mut client_data,
+            );
+            if let Some(finalizer) = finalizer {
+                finalizer(obj, client_data);
+            }
+        }
+
+        #[cfg(feature = "gc-poison-freed")]
+        unsafe {
+            ptr::write_bytes(obj, 0xDB, layout.size());
+        }
+        #[cfg(not(feature = "gc-poison-freed"))]
+        unsafe {
+            bdwgc::GC_free(obj);
+        }
+    }
 }
 
 impl<T> Gc<T> {
@@ -430,6 +700,47 @@ impl<T> Gc<T> {
     pub fn new(value: T) -> Self {
         unsafe { Self::new_internal(value) }
     }
+
+    /// Constructs a new `Pin<Gc<T>>`.
+    ///
+    /// This is sound for any `T`, as the collector never moves the objects it
+    /// allocates, and a `Gc` never gives out a mutable reference to its value.
+    ///
+    /// That also means a `Pin<Gc<T>>` can never be turned into a `Pin<&mut T>`,
+    /// so e.g. a pinned future cannot be polled directly. The intended use is
+    /// to put the `!Unpin` value behind interior mutability, such as a
+    /// [`Mutex`], whose contents are treated as structurally pinned: they are
+    /// only ever reached through the pinned `Gc` and never moved out of.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![feature(gc, noop_waker)]
+    /// use std::future::Future;
+    /// use std::gc::Gc;
+    /// use std::pin::Pin;
+    /// use std::sync::Mutex;
+    /// use std::task::{Context, Poll, Waker};
+    ///
+    /// struct Task<F> {
+    ///     // Structurally pinned.
+    ///     future: Mutex<F>,
+    /// }
+    ///
+    /// let task = Gc::pin(Task { future: Mutex::new(async { 42 }) });
+    ///
+    /// let mut future = task.future.lock().unwrap();
+    /// // SAFETY: `task` is pinned, and `future` is never moved out of it.
+    /// let future = unsafe { Pin::new_unchecked(&mut *future) };
+    /// let mut cx = Context::from_waker(Waker::noop());
+    /// assert_eq!(future.poll(&mut cx), Poll::Ready(42));
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "gc", issue = "none")]
+    #[cfg_attr(not(bootstrap), rustc_fsa_entry_point)]
+    pub fn pin(value: T) -> Pin<Gc<T>> {
+        unsafe { Pin::new_unchecked(Gc::new(value)) }
+    }
 }
 
 impl<T> Gc<T> {
@@ -493,11 +804,41 @@ pub unsafe fn new_unsynchronised(value: T) -> Self {
         unsafe { Self::new_internal(value) }
     }
 
+    /// Returns a clone of the inner value.
+    ///
+    /// This exists for parity with [`Arc::unwrap_or_clone`]. Unlike an `Arc`,
+    /// a `Gc` cannot know whether it is the only pointer to its value, so the
+    /// value is always cloned.
+    ///
+    /// [`Arc::unwrap_or_clone`]: crate::sync::Arc::unwrap_or_clone
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![feature(gc)]
+    /// use std::gc::Gc;
+    ///
+    /// let gc = Gc::new(String::from("eggplant"));
+    /// let inner = Gc::unwrap_or_clone(gc);
+    /// assert_eq!(inner, "eggplant");
+    /// ```
+    #[inline]
+    #[unstable(feature = "gc", issue = "none")]
+    pub fn unwrap_or_clone(this: Self) -> T
+    where
+        T: Clone,
+    {
+        (*this).clone()
+    }
+
     #[inline(always)]
     #[cfg(not(no_global_oom_handling))]
     unsafe fn new_internal(value: T) -> Self {
         #[cfg(not(bootstrap))]
-        if !crate::mem::needs_finalizer::<T>() {
+        if !crate::mem::needs_finalizer::<T>()
+            || (!crate::intrinsics::needs_finalizer_with_gc_allocator::<T>()
+                && gc_is_global_allocator())
+        {
             return Self::from_inner(Box::leak(Box::new_in(GcBox { value }, GcAllocator)).into());
         }
 
@@ -533,6 +874,278 @@ unsafe fn new_internal(value: T) -> Self {
     }
 }
 
+/// Returns `true` if `GcAllocator` is the global allocator, in which case the
+/// collector owns memory allocated through `Global` and the drop methods of
+/// `FinalizerElidableWithGcAllocator` types need not run as finalizers.
+#[cfg(not(bootstrap))]
+#[cfg(not(no_global_oom_handling))]
+fn gc_is_global_allocator() -> bool {
+    use core::sync::atomic::{AtomicU8, Ordering::Relaxed};
+
+    const UNKNOWN: u8 = 0;
+    const NO: u8 = 1;
+    const YES: u8 = 2;
+    static GLOBAL_IS_GC: AtomicU8 = AtomicU8::new(UNKNOWN);
+
+    match GLOBAL_IS_GC.load(Relaxed) {
+        UNKNOWN => {
+            let layout = Layout::new::<usize>();
+            // SAFETY: `layout` has a non-zero size, and the block is freed with
+            // the layout it was allocated with.
+            let is_gc = unsafe {
+                let ptr = crate::alloc::alloc(layout);
+                if ptr.is_null() {
+                    handle_alloc_error(layout);
+                }
+                let is_gc = !bdwgc::GC_base(ptr).is_null();
+                crate::alloc::dealloc(ptr, layout);
+                is_gc
+            };
+            GLOBAL_IS_GC.store(if is_gc { YES } else { NO }, Relaxed);
+            is_gc
+        }
+        state => state == YES,
+    }
+}
+
+/// A finalization queue which hands unreachable values back to the program
+/// instead of dropping them on the finalizer thread.
+///
+/// [`Gc::new`] drops its value on a separate finalizer thread, so the value's
+/// drop method must be safe to run there. Types whose drop logic is tied to
+/// a particular thread (GUI handles, caches full of [`Rc`]s, thread-affine FFI
+/// resources) can instead be registered with a `Guardian`. When a registered
+/// value becomes unreachable, the collector moves it into the guardian's
+/// queue, and it is dropped when the thread which owns the guardian calls
+/// [`poll`].
+///
+/// A `Guardian<T>` is only [`Send`] if `T` is, so a guardian for `!Send`
+/// values stays on the thread which created it. Finalizer safety analysis
+/// still checks the drop methods of registered values, but it allows them to
+/// be `!Send` and `!Sync` and to use thread-locals.
+///
+/// Values which become unreachable after their guardian has been dropped are
+/// never dropped, as with [`Gc::new_unfinalizable`].
+///
+/// [`Rc`]: crate::rc::Rc
+/// [`poll`]: Guardian::poll
+///
+/// # Examples
+///
+/// ```
+/// # #![feature(gc)]
+/// use std::gc::{Guardian, GcAllocator};
+/// use std::rc::Rc;
+///
+/// let guardian = Guardian::new();
+/// let shared = Rc::new(());
+/// let _ = guardian.register(Rc::clone(&shared));
+///
+/// GcAllocator::force_gc();
+/// // The registered `Rc` is dropped here, on this thread, once the collector
+/// // has found it unreachable.
+/// guardian.poll();
+/// ```
+#[unstable(feature = "gc", issue = "none")]
+#[cfg_attr(not(test), rustc_diagnostic_item = "Guardian")]
+pub struct Guardian<T> {
+    queue: Gc<GuardianQueue<T>>,
+}
+
+struct GuardianQueue<T> {
+    pending: Mutex<Vec<T>>,
+}
+
+impl<T> Guardian<T> {
+    /// Creates a guardian with an empty queue.
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "gc", issue = "none")]
+    pub fn new() -> Self {
+        Guardian { queue: Gc::new_unfinalizable(GuardianQueue { pending: Mutex::new(Vec::new()) }) }
+    }
+
+    /// Constructs a new `Gc<T>` whose value is queued on this guardian, rather
+    /// than finalized, once it becomes unreachable.
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "gc", issue = "none")]
+    #[cfg_attr(not(bootstrap), rustc_fsa_entry_point)]
+    #[cfg_attr(not(test), rustc_diagnostic_item = "gc_guardian_register")]
+    pub fn register(&self, value: T) -> Gc<T> {
+        let ptr = Box::leak(Box::new_in(GcBox { value }, GcAllocator));
+        #[cfg(not(bootstrap))]
+        if !crate::mem::needs_finalizer::<T>() {
+            return unsafe { Gc::from_inner(ptr.into()) };
+        }
+
+        // Runs on the finalizer thread. It only moves the value's bytes into the queue, so it
+        // never runs any of `T`'s code there, even if `T` is `!Send`.
+        unsafe extern "C" fn enqueue<T>(obj: *mut u8, queue: *mut u8) {
+            unsafe {
+                let value = ptr::read(ptr::addr_of!((*(obj as *mut GcBox<T>)).value));
+                let queue = &*(queue as *const GuardianQueue<T>);
+                queue.pending.lock().unwrap_or_else(PoisonError::into_inner).push(value);
+            }
+        }
+
+        // The collector traces the client data of registered finalizers, so the queue stays
+        // alive for as long as any value registered with it is.
+        unsafe {
+            bdwgc::GC_register_finalizer_no_order(
+                ptr as *mut _ as *mut u8,
+                Some(enqueue::<T>),
+                Gc::as_ptr(&self.queue) as *mut u8,
+                ptr::null_mut(),
+                ptr::null_mut(),
+            );
+        }
+        #[cfg(feature = "log-stats")]
+        GC_COUNTERS.finalizers_registered.fetch_add(1, atomic::Ordering::Relaxed);
+        unsafe { Gc::from_inner(ptr.into()) }
+    }
+
+    /// Removes a value which the collector has found unreachable from the
+    /// queue, returning it to the caller.
+    #[unstable(feature = "gc", issue = "none")]
+    pub fn pop(&self) -> Option<T> {
+        self.queue.pending.lock().unwrap_or_else(PoisonError::into_inner).pop()
+    }
+
+    /// Drops every value which the collector has found unreachable since the
+    /// last poll, returning how many were dropped.
+    #[unstable(feature = "gc", issue = "none")]
+    pub fn poll(&self) -> usize {
+        // Take the values out first, so that their drop methods can register new values or poll
+        // this guardian again without deadlocking.
+        let pending =
+            mem::take(&mut *self.queue.pending.lock().unwrap_or_else(PoisonError::into_inner));
+        let count = pending.len();
+        drop(pending);
+        count
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "gc", issue = "none")]
+impl<T> Default for Guardian<T> {
+    fn default() -> Self {
+        Guardian::new()
+    }
+}
+
+#[unstable(feature = "gc", issue = "none")]
+impl<T> fmt::Debug for Guardian<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Guardian").finish_non_exhaustive()
+    }
+}
+
+#[unstable(feature = "gc", issue = "none")]
+impl<T> Drop for Guardian<T> {
+    fn drop(&mut self) {
+        self.poll();
+    }
+}
+
+/// The implementation of waking a task on an executor which keeps its tasks
+/// in [`Gc`]s.
+///
+/// This is the counterpart of [`Wake`] for [`Gc`]. Converting a `Gc<W>` into
+/// a [`Waker`] makes a waker which simply copies the pointer when it is
+/// cloned and forgets it when it is dropped, so wakers cost no reference
+/// counting, and a task which refers to its own waker forms a cycle the
+/// collector can free.
+///
+/// [`Wake`]: crate::task::Wake
+///
+/// # Examples
+///
+/// ```
+/// # #![feature(gc)]
+/// use std::gc::{Gc, GcWake};
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use std::task::Waker;
+///
+/// struct Flag(AtomicBool);
+///
+/// impl GcWake for Flag {
+///     fn wake(self: Gc<Self>) {
+///         self.0.store(true, Ordering::Release);
+///     }
+/// }
+///
+/// let flag = Gc::new(Flag(AtomicBool::new(false)));
+/// let waker = Waker::from(flag);
+/// waker.wake_by_ref();
+/// assert!(flag.0.load(Ordering::Acquire));
+/// ```
+#[unstable(feature = "gc", issue = "none")]
+pub trait GcWake {
+    /// Wake this task.
+    #[unstable(feature = "gc", issue = "none")]
+    fn wake(self: Gc<Self>);
+
+    /// Wake this task without consuming the waker.
+    ///
+    /// By default, this copies the [`Gc`] and calls [`wake`] on the copy.
+    ///
+    /// [`wake`]: GcWake::wake
+    #[unstable(feature = "gc", issue = "none")]
+    fn wake_by_ref(self: &Gc<Self>) {
+        (*self).wake();
+    }
+}
+
+#[unstable(feature = "gc", issue = "none")]
+impl<W: GcWake + Send + Sync + 'static> From<Gc<W>> for Waker {
+    /// Use a [`GcWake`]-able type as a `Waker`.
+    ///
+    /// No heap allocations, reference counting, or atomic operations are used
+    /// for this conversion, or when the waker is cloned or dropped.
+    fn from(waker: Gc<W>) -> Waker {
+        // SAFETY: This is safe because gc_raw_waker safely constructs
+        // a RawWaker from Gc<W>.
+        unsafe { Waker::from_raw(gc_raw_waker(waker)) }
+    }
+}
+
+#[unstable(feature = "gc", issue = "none")]
+impl<W: GcWake + Send + Sync + 'static> From<Gc<W>> for RawWaker {
+    /// Use a `GcWake`-able type as a `RawWaker`.
+    fn from(waker: Gc<W>) -> RawWaker {
+        gc_raw_waker(waker)
+    }
+}
+
+// As with `Arc`'s wakers, both `From` impls call this function directly, and
+// it is always inlined so that the vtables of a `W` are deduplicated.
+#[inline(always)]
+fn gc_raw_waker<W: GcWake + Send + Sync + 'static>(waker: Gc<W>) -> RawWaker {
+    // The waker's data pointer keeps the `Gc` alive wherever the waker is
+    // stored, so copying it is all cloning needs to do.
+    #[inline(always)]
+    unsafe fn clone_waker<W: GcWake + Send + Sync + 'static>(waker: *const ()) -> RawWaker {
+        RawWaker::new(
+            waker,
+            &RawWakerVTable::new(clone_waker::<W>, wake::<W>, wake_by_ref::<W>, drop_waker::<W>),
+        )
+    }
+
+    unsafe fn wake<W: GcWake + Send + Sync + 'static>(waker: *const ()) {
+        <W as GcWake>::wake(Gc::from_raw(waker as *const W));
+    }
+
+    unsafe fn wake_by_ref<W: GcWake + Send + Sync + 'static>(waker: *const ()) {
+        <W as GcWake>::wake_by_ref(&Gc::from_raw(waker as *const W));
+    }
+
+    unsafe fn drop_waker<W: GcWake + Send + Sync + 'static>(_: *const ()) {}
+
+    RawWaker::new(
+        Gc::into_raw(waker) as *const (),
+        &RawWakerVTable::new(clone_waker::<W>, wake::<W>, wake_by_ref::<W>, drop_waker::<W>),
+    )
+}
+
 #[cfg(profile_gc)]
 #[derive(Debug)]
 pub struct FinalizerInfo {
@@ -620,6 +1233,76 @@ pub unsafe fn downcast_unchecked<T: Any>(self) -> Gc<T> {
     }
 }
 
+impl Gc<dyn Any + Send + Sync> {
+    /// Attempt to downcast the `Gc<dyn Any + Send + Sync>` to a concrete type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![feature(gc)]
+    /// use std::any::Any;
+    /// use std::gc::Gc;
+    ///
+    /// fn print_if_string(value: Gc<dyn Any + Send + Sync>) {
+    ///     if let Ok(string) = value.downcast::<String>() {
+    ///         println!("String ({}): {}", string.len(), string);
+    ///     }
+    /// }
+    ///
+    /// let my_string = "Hello World".to_string();
+    /// print_if_string(Gc::new(my_string));
+    /// print_if_string(Gc::new(0i8));
+    /// ```
+    #[inline]
+    #[unstable(feature = "gc", issue = "none")]
+    pub fn downcast<T: Any + Send + Sync>(self) -> Result<Gc<T>, Self> {
+        if (*self).is::<T>() {
+            unsafe {
+                let ptr = self.ptr.cast::<GcBox<T>>();
+                Ok(Gc::from_inner(ptr))
+            }
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Downcasts the `Gc<dyn Any + Send + Sync>` to a concrete type.
+    ///
+    /// For a safe alternative see [`downcast`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![feature(gc)]
+    /// #![feature(downcast_unchecked)]
+    ///
+    /// use std::any::Any;
+    /// use std::gc::Gc;
+    ///
+    /// let x: Gc<dyn Any + Send + Sync> = Gc::new(1_usize);
+    ///
+    /// unsafe {
+    ///     assert_eq!(*x.downcast_unchecked::<usize>(), 1);
+    /// }
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// The contained value must be of type `T`. Calling this method
+    /// with the incorrect type is *undefined behavior*.
+    ///
+    ///
+    /// [`downcast`]: Self::downcast
+    #[inline]
+    #[unstable(feature = "downcast_unchecked", issue = "90850")]
+    pub unsafe fn downcast_unchecked<T: Any + Send + Sync>(self) -> Gc<T> {
+        unsafe {
+            let ptr = self.ptr.cast::<GcBox<T>>();
+            Gc::from_inner(ptr)
+        }
+    }
+}
+
 impl<T: Send + Sync> Gc<MaybeUninit<T>> {
     /// As with `MaybeUninit::assume_init`, it is up to the caller to guarantee
     /// that the inner value really is in an initialized state. Calling this
@@ -815,6 +1498,199 @@ fn from(v: Vec<T>) -> Gc<[T]> {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "gc", issue = "none")]
+impl<'a, B> From<Cow<'a, B>> for Gc<B>
+where
+    B: ToOwned + ?Sized,
+    Gc<B>: From<&'a B> + From<B::Owned>,
+{
+    /// Create a garbage-collected pointer from a clone-on-write pointer by
+    /// copying its content.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #![feature(gc)]
+    /// # use std::gc::Gc;
+    /// # use std::borrow::Cow;
+    /// let cow: Cow<'_, str> = Cow::Borrowed("eggplant");
+    /// let shared: Gc<str> = Gc::from(cow);
+    /// assert_eq!("eggplant", &shared[..]);
+    /// ```
+    #[inline]
+    #[cfg_attr(not(bootstrap), rustc_fsa_entry_point)]
+    fn from(cow: Cow<'a, B>) -> Gc<B> {
+        match cow {
+            Cow::Borrowed(s) => Gc::from(s),
+            Cow::Owned(s) => Gc::from(s),
+        }
+    }
+}
+
+#[unstable(feature = "gc", issue = "none")]
+impl From<Gc<str>> for Gc<[u8]> {
+    /// Converts a garbage-collected string slice into a byte slice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #![feature(gc)]
+    /// # use std::gc::Gc;
+    /// let string: Gc<str> = Gc::from("eggplant");
+    /// let bytes: Gc<[u8]> = Gc::from(string);
+    /// assert_eq!("eggplant".as_bytes(), bytes.as_ref());
+    /// ```
+    #[inline]
+    fn from(gc: Gc<str>) -> Self {
+        // `str` has the same layout as `[u8]`.
+        Gc::from_raw(Gc::into_raw(gc) as *const [u8])
+    }
+}
+
+#[unstable(feature = "gc", issue = "none")]
+impl<T, const N: usize> TryFrom<Gc<[T]>> for Gc<[T; N]> {
+    type Error = Gc<[T]>;
+
+    fn try_from(slice: Gc<[T]>) -> Result<Self, Self::Error> {
+        if slice.len() == N {
+            Ok(unsafe { Gc::from_inner(slice.ptr.cast()) })
+        } else {
+            Err(slice)
+        }
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "gc", issue = "none")]
+impl From<&CStr> for Gc<CStr> {
+    /// Copies the string into a newly allocated <code>[Gc]&lt;[CStr]&gt;</code>.
+    #[inline]
+    fn from(s: &CStr) -> Gc<CStr> {
+        let gc = Gc::<[u8]>::from(s.to_bytes_with_nul());
+        Gc::from_raw(Gc::into_raw(gc) as *const CStr)
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "gc", issue = "none")]
+impl From<CString> for Gc<CStr> {
+    /// Converts a [`CString`] into a <code>[Gc]<[CStr]></code> by copying the
+    /// [`CString`] data into a new [`Gc`] buffer.
+    #[inline]
+    fn from(s: CString) -> Gc<CStr> {
+        Gc::from(s.as_c_str())
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "gc", issue = "none")]
+impl From<&OsStr> for Gc<OsStr> {
+    /// Copies the string into a newly allocated <code>[Gc]&lt;[OsStr]&gt;</code>.
+    #[inline]
+    fn from(s: &OsStr) -> Gc<OsStr> {
+        let gc = Gc::<[u8]>::from(s.as_encoded_bytes());
+        Gc::from_raw(Gc::into_raw(gc) as *const OsStr)
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "gc", issue = "none")]
+impl From<OsString> for Gc<OsStr> {
+    /// Converts an [`OsString`] into a <code>[Gc]<[OsStr]></code> by copying
+    /// the [`OsString`] data into a new [`Gc`] buffer.
+    #[inline]
+    fn from(s: OsString) -> Gc<OsStr> {
+        Gc::from(s.as_os_str())
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "gc", issue = "none")]
+impl From<&Path> for Gc<Path> {
+    /// Converts a [`Path`] into a [`Gc`] by copying the [`Path`] data into a
+    /// new [`Gc`] buffer.
+    #[inline]
+    fn from(s: &Path) -> Gc<Path> {
+        let gc: Gc<OsStr> = Gc::from(s.as_os_str());
+        Gc::from_raw(Gc::into_raw(gc) as *const Path)
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "gc", issue = "none")]
+impl From<PathBuf> for Gc<Path> {
+    /// Converts a [`PathBuf`] into a <code>[Gc]<[Path]></code> by copying the
+    /// [`PathBuf`] data into a new [`Gc`] buffer.
+    #[inline]
+    fn from(s: PathBuf) -> Gc<Path> {
+        Gc::from(s.as_path())
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "gc", issue = "none")]
+impl<T> FromIterator<T> for Gc<[T]> {
+    /// Takes each element in the `Iterator` and collects it into a `Gc<[T]>`.
+    ///
+    /// In the general case, this first collects into a `Vec<T>`, and then
+    /// moves its elements into a new `Gc<[T]>`. When the iterator implements
+    /// `TrustedLen` and is of an exact size, a single allocation is made for
+    /// the `Gc<[T]>` instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #![feature(gc)]
+    /// # use std::gc::Gc;
+    /// let evens: Gc<[u8]> = (0..10).filter(|&x| x % 2 == 0).collect();
+    /// assert_eq!(&[0, 2, 4, 6, 8], &evens[..]);
+    ///
+    /// let all: Gc<[u8]> = (0..10).collect(); // Just a single allocation happens here.
+    /// assert_eq!(&*all, &*(0..10).collect::<Vec<_>>());
+    /// ```
+    #[cfg_attr(not(bootstrap), rustc_fsa_entry_point)]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        ToGcSlice::to_gc_slice(iter.into_iter())
+    }
+}
+
+/// Specialization trait used for collecting into `Gc<[T]>`.
+#[cfg(not(no_global_oom_handling))]
+trait ToGcSlice<T>: Iterator<Item = T> + Sized {
+    fn to_gc_slice(self) -> Gc<[T]>;
+}
+
+#[cfg(not(no_global_oom_handling))]
+impl<T, I: Iterator<Item = T>> ToGcSlice<T> for I {
+    default fn to_gc_slice(self) -> Gc<[T]> {
+        self.collect::<Vec<T>>().into()
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+impl<T, I: TrustedLen<Item = T>> ToGcSlice<T> for I {
+    fn to_gc_slice(self) -> Gc<[T]> {
+        let (low, high) = self.size_hint();
+        if let Some(high) = high {
+            debug_assert_eq!(
+                low,
+                high,
+                "TrustedLen iterator's size hint is not exact: {:?}",
+                (low, high)
+            );
+
+            // SAFETY: The iterator has exactly `low` elements.
+            unsafe { Gc::from_iter_exact(self, low) }
+        } else {
+            // `TrustedLen` guarantees that `high == None` implies a length
+            // exceeding `usize::MAX`, which collecting into a `Vec` would
+            // panic on anyway.
+            panic!("capacity overflow");
+        }
+    }
+}
+
 /// Specialization trait used for `From<&[T]>`.
 #[cfg(not(no_global_oom_handling))]
 trait GcFromSlice<T> {
@@ -1106,6 +1982,27 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+#[unstable(feature = "gc", issue = "none")]
+impl<T: core::error::Error + ?Sized> core::error::Error for Gc<T> {
+    #[allow(deprecated, deprecated_in_future)]
+    fn description(&self) -> &str {
+        core::error::Error::description(&**self)
+    }
+
+    #[allow(deprecated)]
+    fn cause(&self) -> Option<&dyn core::error::Error> {
+        core::error::Error::cause(&**self)
+    }
+
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        core::error::Error::source(&**self)
+    }
+
+    fn provide<'a>(&'a self, req: &mut core::error::Request<'a>) {
+        core::error::Error::provide(&**self, req);
+    }
+}
+
 #[unstable(feature = "gc", issue = "none")]
 impl<T: ?Sized> fmt::Pointer for Gc<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
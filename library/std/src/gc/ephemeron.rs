@@ -0,0 +1,332 @@
+//! Ephemeron tables: maps keyed by `Gc` objects which keep each value alive
+//! only for as long as its key is reachable from somewhere else.
+//!
+//! Entries live in lists of cells shared by every map. A cell holds its key
+//! and value as hidden pointers, so the collector does not trace through it.
+//! Instead, a hook which runs whenever the collector's mark stack is empty
+//! marks the value of every cell whose key has already been marked. This
+//! repeats until no new values are marked, so a value which refers back to
+//! its own key does not keep that key alive. A cell is resolved once its value
+//! is marked or its key is dead, and the hook skips it for the rest of that
+//! mark phase. Each cell's key field is also registered as a disappearing
+//! link, so the collector clears it once the key is unreachable, and the map
+//! can tell that the entry is dead.
+
+use core::alloc::{Allocator, Layout};
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem;
+use core::ptr::{self, NonNull};
+use core::sync::atomic::{AtomicPtr, AtomicUsize, Ordering::Relaxed};
+
+use super::{hide, reveal, with_alloc_lock, Gc, GcAllocator};
+use crate::alloc::handle_alloc_error;
+use crate::collections::HashMap;
+use crate::sync::Once;
+
+// The lists below are only modified with the collector's allocation lock held,
+// which orders every access to them, so `Relaxed` is enough.
+
+/// The head of the list of cells which have not been resolved in the current
+/// mark phase. Between collections, this holds every cell.
+static PENDING: AtomicPtr<EphemeronCell> = AtomicPtr::new(ptr::null_mut());
+
+/// The head of the list of cells which were resolved in the mark phase of
+/// collection number `RESOLVED_IN`.
+static RESOLVED: AtomicPtr<EphemeronCell> = AtomicPtr::new(ptr::null_mut());
+static RESOLVED_IN: AtomicUsize = AtomicUsize::new(0);
+
+/// The mark stack empty hook which was installed before ours, if any.
+static PREVIOUS_MARK_HOOK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+static INSTALL_MARK_HOOK: Once = Once::new();
+
+type MarkStackEmptyProc = unsafe extern "C" fn(*mut u8, *mut u8) -> *mut u8;
+
+#[repr(C)]
+struct EphemeronCell {
+    /// The hidden address of the key, or zero once the collector has found the
+    /// key unreachable.
+    key: usize,
+    /// The hidden address of the value.
+    value: usize,
+    prev: *mut EphemeronCell,
+    next: *mut EphemeronCell,
+}
+
+/// Whether the collector has marked the object `ptr` points into. Pointers
+/// which are not into the GC heap (e.g. zero-sized allocations) are always
+/// considered marked.
+unsafe fn is_marked(ptr: *mut u8) -> bool {
+    let base = unsafe { bdwgc::GC_base(ptr) };
+    base.is_null() || unsafe { bdwgc::GC_is_marked(base) } != 0
+}
+
+/// Installs `mark_ephemerons`, chaining to any hook which was installed before.
+fn install_mark_hook() {
+    INSTALL_MARK_HOOK.call_once(|| unsafe {
+        // The collector only calls our hook once it is installed, which it does
+        // with the allocation lock held, so it will see this store.
+        let previous = bdwgc::GC_get_mark_stack_empty();
+        PREVIOUS_MARK_HOOK.store(previous.map_or(ptr::null_mut(), |f| f as *mut ()), Relaxed);
+        bdwgc::GC_set_mark_stack_empty(Some(mark_ephemerons));
+    });
+}
+
+/// Called by the collector, with the allocation lock held, whenever its mark
+/// stack is empty.
+unsafe extern "C" fn mark_ephemerons(mut msp: *mut u8, msl: *mut u8) -> *mut u8 {
+    unsafe {
+        // Cells resolved in an earlier collection must be checked again.
+        let gc_no = bdwgc::GC_get_gc_no() as usize;
+        if RESOLVED_IN.swap(gc_no, Relaxed) != gc_no {
+            let mut cell = RESOLVED.swap(ptr::null_mut(), Relaxed);
+            while !cell.is_null() {
+                let next = (*cell).next;
+                push(&PENDING, cell);
+                cell = next;
+            }
+        }
+
+        let mut cell = PENDING.load(Relaxed);
+        while !cell.is_null() {
+            let EphemeronCell { key, value, next, .. } = *cell;
+            if key == 0 || is_marked(reveal(value)) {
+                remove(cell);
+                push(&RESOLVED, cell);
+            } else if is_marked(reveal(key)) {
+                let base = bdwgc::GC_base(reveal(value));
+                msp = bdwgc::GC_mark_and_push(base, msp, msl, ptr::null_mut());
+                remove(cell);
+                push(&RESOLVED, cell);
+            }
+            cell = next;
+        }
+
+        let previous = PREVIOUS_MARK_HOOK.load(Relaxed);
+        if !previous.is_null() {
+            let previous = mem::transmute::<*mut (), MarkStackEmptyProc>(previous);
+            msp = previous(msp, msl);
+        }
+    }
+    msp
+}
+
+/// Pushes `cell` onto the front of `list`. The allocation lock must be held.
+unsafe fn push(list: &AtomicPtr<EphemeronCell>, cell: *mut EphemeronCell) {
+    unsafe {
+        let head = list.load(Relaxed);
+        (*cell).prev = ptr::null_mut();
+        (*cell).next = head;
+        if !head.is_null() {
+            (*head).prev = cell;
+        }
+        list.store(cell, Relaxed);
+    }
+}
+
+/// Removes `cell` from whichever list holds it. The allocation lock must be
+/// held.
+unsafe fn remove(cell: *mut EphemeronCell) {
+    unsafe {
+        let EphemeronCell { prev, next, .. } = *cell;
+        if prev.is_null() {
+            let list = if PENDING.load(Relaxed) == cell { &PENDING } else { &RESOLVED };
+            list.store(next, Relaxed);
+        } else {
+            (*prev).next = next;
+        }
+        if !next.is_null() {
+            (*next).prev = prev;
+        }
+    }
+}
+
+/// Adds `cell` to the cells the collector traces.
+unsafe fn link(cell: NonNull<EphemeronCell>) {
+    with_alloc_lock(|| unsafe { push(&PENDING, cell.as_ptr()) });
+}
+
+/// Removes `cell` from the cells the collector traces.
+unsafe fn unlink(cell: NonNull<EphemeronCell>) {
+    let cell = cell.as_ptr();
+    unsafe { bdwgc::GC_unregister_disappearing_link(ptr::addr_of_mut!((*cell).key).cast()) };
+    with_alloc_lock(|| unsafe { remove(cell) });
+}
+
+/// A map from `Gc<K>` keys to `Gc<V>` values, where an entry keeps its value
+/// alive only while its key is reachable from outside the map.
+///
+/// Keys are compared by identity, using the address returned by
+/// [`Gc::as_ptr`], not by `K`'s [`Eq`] implementation. The map never keeps a
+/// key alive, and once the collector finds a key unreachable its entry
+/// disappears, even if the value refers back to the key. This makes it
+/// suitable for attaching side data (memo tables, debug info, JIT caches) to
+/// objects the map does not own.
+///
+/// # Examples
+///
+/// ```
+/// # #![feature(gc)]
+/// use std::gc::{EphemeronMap, Gc};
+///
+/// let mut names = EphemeronMap::new();
+/// let key = Gc::new(42);
+/// names.insert(key, Gc::new("forty-two"));
+///
+/// assert_eq!(names.get(&key).as_deref(), Some(&"forty-two"));
+/// assert!(!names.contains_key(&Gc::new(42)));
+/// ```
+#[unstable(feature = "gc", issue = "none")]
+pub struct EphemeronMap<K: ?Sized, V> {
+    /// The cell of each entry, keyed by the hidden address of its key.
+    cells: HashMap<usize, NonNull<EphemeronCell>>,
+    /// The number of entries the last time dead entries were removed.
+    live_at_last_purge: usize,
+    _marker: PhantomData<(Gc<K>, Gc<V>)>,
+}
+
+impl<K: ?Sized, V> EphemeronMap<K, V> {
+    /// Creates an empty `EphemeronMap`.
+    #[unstable(feature = "gc", issue = "none")]
+    pub fn new() -> Self {
+        install_mark_hook();
+        EphemeronMap { cells: HashMap::new(), live_at_last_purge: 0, _marker: PhantomData }
+    }
+
+    /// Returns the cell of `key`'s entry, if it has one.
+    fn cell(&self, key: &Gc<K>) -> Option<NonNull<EphemeronCell>> {
+        let cell = *self.cells.get(&hide(Gc::as_ptr(key)))?;
+        // A cleared key means that the cell belongs to an earlier object which
+        // lived at the same address.
+        (unsafe { cell.as_ref().key } != 0).then_some(cell)
+    }
+
+    /// # Safety
+    ///
+    /// The key of `cell` must be reachable.
+    unsafe fn value(cell: NonNull<EphemeronCell>) -> Gc<V> {
+        // The key is reachable, so the collector has kept the value alive too.
+        Gc::from_raw(reveal(unsafe { cell.as_ref().value }) as *const V)
+    }
+
+    /// Returns the value associated with `key`.
+    #[unstable(feature = "gc", issue = "none")]
+    pub fn get(&self, key: &Gc<K>) -> Option<Gc<V>> {
+        self.cell(key).map(|cell| unsafe { Self::value(cell) })
+    }
+
+    /// Returns `true` if the map has a value for `key`.
+    #[unstable(feature = "gc", issue = "none")]
+    pub fn contains_key(&self, key: &Gc<K>) -> bool {
+        self.cell(key).is_some()
+    }
+
+    /// Associates `value` with `key`, returning the value previously
+    /// associated with it, if any.
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "gc", issue = "none")]
+    pub fn insert(&mut self, key: Gc<K>, value: Gc<V>) -> Option<Gc<V>> {
+        if let Some(mut cell) = self.cell(&key) {
+            let old = unsafe { Self::value(cell) };
+            // The new value may not be marked yet, so the cell is no longer resolved.
+            with_alloc_lock(|| unsafe {
+                cell.as_mut().value = hide(Gc::as_ptr(&value));
+                remove(cell.as_ptr());
+                push(&PENDING, cell.as_ptr());
+            });
+            return Some(old);
+        }
+        if self.cells.len() >= 2 * self.live_at_last_purge.max(8) {
+            self.purge();
+        }
+
+        let layout = Layout::new::<EphemeronCell>();
+        let cell = GcAllocator
+            .allocate(layout)
+            .unwrap_or_else(|_| handle_alloc_error(layout))
+            .cast::<EphemeronCell>();
+        unsafe {
+            cell.as_ptr().write(EphemeronCell {
+                key: hide(Gc::as_ptr(&key)),
+                value: hide(Gc::as_ptr(&value)),
+                prev: ptr::null_mut(),
+                next: ptr::null_mut(),
+            });
+            let key_base = bdwgc::GC_base(Gc::as_ptr(&key) as *mut u8);
+            if !key_base.is_null() {
+                bdwgc::GC_general_register_disappearing_link(
+                    ptr::addr_of_mut!((*cell.as_ptr()).key).cast(),
+                    key_base,
+                );
+            }
+            link(cell);
+        }
+        if let Some(stale) = self.cells.insert(hide(Gc::as_ptr(&key)), cell) {
+            unsafe { unlink(stale) };
+        }
+        None
+    }
+
+    /// Removes the entry for `key`, returning its value.
+    #[unstable(feature = "gc", issue = "none")]
+    pub fn remove(&mut self, key: &Gc<K>) -> Option<Gc<V>> {
+        let cell = self.cell(key)?;
+        self.cells.remove(&hide(Gc::as_ptr(key)));
+        let value = unsafe { Self::value(cell) };
+        unsafe { unlink(cell) };
+        Some(value)
+    }
+
+    /// Returns the number of entries whose keys are still reachable.
+    ///
+    /// This is only an upper bound on the number of keys which are reachable
+    /// from outside the map, as keys which became unreachable since the last
+    /// collection are still counted.
+    #[unstable(feature = "gc", issue = "none")]
+    pub fn len(&self) -> usize {
+        self.cells.values().filter(|cell| unsafe { cell.as_ref().key } != 0).count()
+    }
+
+    /// Returns `true` if the map has no entries whose keys are still
+    /// reachable.
+    #[unstable(feature = "gc", issue = "none")]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Forgets the entries whose keys the collector has found unreachable.
+    fn purge(&mut self) {
+        self.cells.retain(|_, cell| {
+            let live = unsafe { cell.as_ref().key } != 0;
+            if !live {
+                unsafe { unlink(*cell) };
+            }
+            live
+        });
+        self.live_at_last_purge = self.cells.len();
+    }
+}
+
+#[unstable(feature = "gc", issue = "none")]
+impl<K: ?Sized, V> Default for EphemeronMap<K, V> {
+    fn default() -> Self {
+        EphemeronMap::new()
+    }
+}
+
+#[unstable(feature = "gc", issue = "none")]
+impl<K: ?Sized, V> fmt::Debug for EphemeronMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EphemeronMap").field("len", &self.len()).finish_non_exhaustive()
+    }
+}
+
+#[unstable(feature = "gc", issue = "none")]
+impl<K: ?Sized, V> Drop for EphemeronMap<K, V> {
+    fn drop(&mut self) {
+        for (_, cell) in self.cells.drain() {
+            unsafe { unlink(cell) };
+        }
+    }
+}
@@ -0,0 +1,367 @@
+//! Isolated heaps: groups of GC objects which are freed all at once when the
+//! group is no longer needed.
+//!
+//! Heaps are isolated for allocation, statistics and teardown only. They are
+//! not collected independently: BDWGC only ever collects the whole process,
+//! so a heap's unreachable objects are found by the same full collections,
+//! which stop every thread, as the rest of the program's.
+//!
+//! Every isolated heap allocates from an object kind of its own, which they
+//! all share, so their objects never share a block with objects made by
+//! `Gc::new`. Each object starts with a header holding the id of its heap, and
+//! the heap remembers the object in a slot registered as a disappearing link,
+//! which the collector clears if it frees the object first. Dropping the heap
+//! frees every object whose slot is still set, without running any finalizers.
+
+use core::alloc::Layout;
+use core::fmt;
+use core::marker::{PhantomData, Unsize};
+use core::mem;
+use core::ops::{CoerceUnsized, Deref};
+use core::ptr::{self, NonNull};
+
+use super::{hide, reveal, with_alloc_lock};
+use crate::alloc::handle_alloc_error;
+use crate::sync::atomic::{AtomicUsize, Ordering};
+use crate::sync::{Mutex, OnceLock, PoisonError};
+
+/// The size of the header before each object, which is also the largest
+/// alignment an object can have.
+const HEADER: usize = 16;
+
+/// The number of slots in the first chunk a heap allocates.
+const MIN_SLOTS: usize = 64;
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+
+/// The object kind shared by every isolated heap.
+fn kind() -> i32 {
+    static KIND: OnceLock<i32> = OnceLock::new();
+    // A descriptor of zero, with the object's size added to it, makes the
+    // collector scan each object conservatively over its whole length.
+    *KIND.get_or_init(|| unsafe { bdwgc::GC_new_kind(bdwgc::GC_new_free_list(), 0, 1, 1) as i32 })
+}
+
+/// A heap of GC objects which can be freed in bulk.
+///
+/// Objects are allocated in the heap with [`alloc`], which returns a
+/// [`HeapGc`] that cannot outlive the heap. The collector frees an object as
+/// soon as it is unreachable, as with [`Gc`], and dropping the heap frees all
+/// of its objects which are left at once. Values in a heap are never dropped,
+/// as with [`Gc::new_unfinalizable`].
+///
+/// A heap cannot be collected on its own. Its garbage is found by the same
+/// full collections as the rest of the program's, which stop every thread.
+///
+/// When the standard library is built with debug assertions, [`alloc`] panics
+/// if the value it is given holds a pointer to an object in another `GcHeap`,
+/// as that object would be freed along with its heap. Only the value's own
+/// words are checked, not the memory they point to. Objects made by
+/// [`Gc::new`] may be referred to from any heap.
+///
+/// [`alloc`]: GcHeap::alloc
+/// [`Gc`]: super::Gc
+/// [`Gc::new`]: super::Gc::new
+/// [`Gc::new_unfinalizable`]: super::Gc::new_unfinalizable
+///
+/// # Examples
+///
+/// ```
+/// # #![feature(gc)]
+/// use std::gc::{GcHeap, HeapGc};
+///
+/// struct Node<'h> {
+///     value: u32,
+///     next: Option<HeapGc<'h, Node<'h>>>,
+/// }
+///
+/// let heap = GcHeap::new();
+/// let tail = heap.alloc(Node { value: 2, next: None });
+/// let head = heap.alloc(Node { value: 1, next: Some(tail) });
+/// assert_eq!(head.next.unwrap().value, 2);
+/// assert_eq!(heap.stats().allocated_objects, 2);
+///
+/// // Frees both nodes.
+/// drop(heap);
+/// ```
+#[unstable(feature = "gc", issue = "none")]
+pub struct GcHeap {
+    id: usize,
+    objects: Mutex<Objects>,
+    allocated_objects: AtomicUsize,
+    allocated_bytes: AtomicUsize,
+}
+
+/// The slots which remember a heap's objects.
+struct Objects {
+    /// Each slot holds the hidden address of an object, or zero once the
+    /// collector has freed it. Slots never move, as the collector writes to
+    /// them.
+    chunks: Vec<Box<[usize]>>,
+    /// Slots which were zero when last looked at.
+    free: Vec<*mut usize>,
+}
+
+unsafe impl Send for Objects {}
+
+impl Objects {
+    fn free_slot(&mut self) -> *mut usize {
+        if self.free.is_empty() {
+            self.purge();
+        }
+        self.free.pop().unwrap()
+    }
+
+    /// Looks for slots whose object was freed, and adds a chunk if less than
+    /// half of the slots are free, so that looking again is only worth it
+    /// after as many allocations as there are slots.
+    fn purge(&mut self) {
+        let mut total = 0;
+        for chunk in &mut self.chunks {
+            total += chunk.len();
+            self.free
+                .extend(chunk.iter_mut().filter(|slot| **slot == 0).map(|slot| slot as *mut usize));
+        }
+        if self.free.len() < total / 2 + 1 {
+            let mut chunk = vec![0; total.max(MIN_SLOTS)].into_boxed_slice();
+            self.free.extend(chunk.iter_mut().map(|slot| slot as *mut usize));
+            self.chunks.push(chunk);
+        }
+    }
+}
+
+/// Statistics about the objects in a [`GcHeap`].
+#[unstable(feature = "gc", issue = "none")]
+#[derive(Debug, Copy, Clone)]
+pub struct GcHeapStats {
+    /// The number of objects allocated in the heap.
+    pub allocated_objects: usize,
+    /// The number of bytes allocated in the heap, including the header of
+    /// each object.
+    pub allocated_bytes: usize,
+    /// The number of objects the collector has not freed yet. This includes
+    /// objects which became unreachable since the last collection.
+    pub live_objects: usize,
+    /// The number of bytes the collector uses for the objects which it has
+    /// not freed yet.
+    pub live_bytes: usize,
+}
+
+impl GcHeap {
+    /// Creates an empty heap.
+    #[unstable(feature = "gc", issue = "none")]
+    pub fn new() -> Self {
+        GcHeap {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            objects: Mutex::new(Objects { chunks: Vec::new(), free: Vec::new() }),
+            allocated_objects: AtomicUsize::new(0),
+            allocated_bytes: AtomicUsize::new(0),
+        }
+    }
+
+    /// Moves `value` into a new object in this heap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` is aligned to more than 16 bytes, or, when the standard
+    /// library is built with debug assertions, if `value` holds a pointer to an
+    /// object in another heap.
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "gc", issue = "none")]
+    pub fn alloc<T>(&self, value: T) -> HeapGc<'_, T> {
+        assert!(
+            mem::align_of::<T>() <= HEADER,
+            "`GcHeap` does not support values aligned to more than {HEADER} bytes"
+        );
+        let size = HEADER + mem::size_of::<T>();
+        let base = unsafe { bdwgc::GC_generic_malloc(size, kind()) };
+        if base.is_null() {
+            handle_alloc_error(Layout::from_size_align(size, HEADER).unwrap());
+        }
+        let ptr = unsafe {
+            (base as *mut usize).write(self.id);
+            let ptr = base.add(HEADER) as *mut T;
+            ptr.write(value);
+            #[cfg(debug_assertions)]
+            self.check_references(ptr as *const u8, mem::size_of::<T>());
+            NonNull::new_unchecked(ptr)
+        };
+
+        let mut objects = self.objects.lock().unwrap_or_else(PoisonError::into_inner);
+        let slot = objects.free_slot();
+        unsafe {
+            *slot = hide(base);
+            bdwgc::GC_general_register_disappearing_link(slot.cast(), base);
+        }
+        drop(objects);
+        self.allocated_objects.fetch_add(1, Ordering::Relaxed);
+        self.allocated_bytes.fetch_add(size, Ordering::Relaxed);
+        HeapGc { ptr, _heap: PhantomData }
+    }
+
+    /// Panics if any of the `len` bytes at `ptr` seem to point to an object in
+    /// another heap. Like the collector, this treats every word as a possible
+    /// pointer. Other memory is not followed, as other threads may be writing
+    /// to it.
+    #[cfg(debug_assertions)]
+    unsafe fn check_references(&self, ptr: *const u8, len: usize) {
+        for i in 0..len / mem::size_of::<usize>() {
+            let word = unsafe { (ptr as *const usize).add(i).read() };
+            let base = unsafe { bdwgc::GC_base(word as *mut u8) };
+            if base.is_null()
+                || unsafe { bdwgc::GC_get_kind_and_size(base, ptr::null_mut()) } != kind()
+            {
+                continue;
+            }
+            // The header is written before the object is handed out, and never again.
+            if unsafe { *(base as *const usize) } != self.id {
+                panic!("an object in a `GcHeap` may not refer to an object in another `GcHeap`");
+            }
+        }
+    }
+
+    /// Returns statistics about the objects in this heap.
+    #[unstable(feature = "gc", issue = "none")]
+    pub fn stats(&self) -> GcHeapStats {
+        let objects = self.objects.lock().unwrap_or_else(PoisonError::into_inner);
+        let (mut live_objects, mut live_bytes) = (0, 0);
+        // With the allocation lock held, no slot is cleared while it is read.
+        with_alloc_lock(|| {
+            for &slot in objects.chunks.iter().flat_map(|chunk| chunk.iter()) {
+                if slot != 0 {
+                    live_objects += 1;
+                    live_bytes += unsafe { bdwgc::GC_size(reveal(slot)) };
+                }
+            }
+        });
+        GcHeapStats {
+            allocated_objects: self.allocated_objects.load(Ordering::Relaxed),
+            allocated_bytes: self.allocated_bytes.load(Ordering::Relaxed),
+            live_objects,
+            live_bytes,
+        }
+    }
+}
+
+#[unstable(feature = "gc", issue = "none")]
+impl Default for GcHeap {
+    fn default() -> Self {
+        GcHeap::new()
+    }
+}
+
+#[unstable(feature = "gc", issue = "none")]
+impl fmt::Debug for GcHeap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GcHeap").field("id", &self.id).finish_non_exhaustive()
+    }
+}
+
+#[unstable(feature = "gc", issue = "none")]
+impl Drop for GcHeap {
+    fn drop(&mut self) {
+        let objects = self.objects.get_mut().unwrap_or_else(PoisonError::into_inner);
+        let total: usize = objects.chunks.iter().map(|chunk| chunk.len()).sum();
+        if total == 0 {
+            return;
+        }
+
+        // Copy the address of every object the collector has not freed yet
+        // into memory it scans, so that it cannot free them before we do.
+        let layout = Layout::array::<*mut u8>(total).unwrap();
+        let live = unsafe { bdwgc::GC_malloc_uncollectable(layout.size()) } as *mut *mut u8;
+        if live.is_null() {
+            handle_alloc_error(layout);
+        }
+        let mut len = 0;
+        with_alloc_lock(|| {
+            for &slot in objects.chunks.iter().flat_map(|chunk| chunk.iter()) {
+                if slot != 0 {
+                    unsafe { *live.add(len) = reveal(slot) };
+                    len += 1;
+                }
+            }
+        });
+
+        unsafe {
+            for chunk in &mut objects.chunks {
+                for slot in chunk.iter_mut() {
+                    bdwgc::GC_unregister_disappearing_link((slot as *mut usize).cast());
+                }
+            }
+            for i in 0..len {
+                bdwgc::GC_free(*live.add(i));
+            }
+            bdwgc::GC_free(live as *mut u8);
+        }
+    }
+}
+
+/// A pointer to an object in a [`GcHeap`], which cannot outlive the heap.
+///
+/// Like [`Gc`], a `HeapGc` is `Copy`, and the object it points to is freed
+/// once the collector finds it unreachable. Its value is never dropped.
+///
+/// [`Gc`]: super::Gc
+#[unstable(feature = "gc", issue = "none")]
+pub struct HeapGc<'h, T: ?Sized> {
+    ptr: NonNull<T>,
+    _heap: PhantomData<&'h GcHeap>,
+}
+
+unsafe impl<T: ?Sized + Send> Send for HeapGc<'_, T> {}
+unsafe impl<T: ?Sized + Sync + Send> Sync for HeapGc<'_, T> {}
+
+// The heap may already have been dropped by the time a finalizer runs.
+impl<T: ?Sized> !core::marker::FinalizerSafe for HeapGc<'_, T> {}
+
+#[unstable(feature = "gc", issue = "none")]
+impl<'h, T: ?Sized + Unsize<U>, U: ?Sized> CoerceUnsized<HeapGc<'h, U>> for HeapGc<'h, T> {}
+
+impl<'h, T: ?Sized> HeapGc<'h, T> {
+    /// Get a raw pointer to the underlying value `T`.
+    #[unstable(feature = "gc", issue = "none")]
+    pub fn as_ptr(this: &Self) -> *const T {
+        this.ptr.as_ptr()
+    }
+
+    #[unstable(feature = "gc", issue = "none")]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        ptr::addr_eq(this.ptr.as_ptr(), other.ptr.as_ptr())
+    }
+}
+
+#[unstable(feature = "gc", issue = "none")]
+impl<T: ?Sized> Deref for HeapGc<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+#[unstable(feature = "gc", issue = "none")]
+impl<T: ?Sized> Copy for HeapGc<'_, T> {}
+
+#[unstable(feature = "gc", issue = "none")]
+impl<T: ?Sized> Clone for HeapGc<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+#[unstable(feature = "gc", issue = "none")]
+impl<T: ?Sized + fmt::Debug> fmt::Debug for HeapGc<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+#[unstable(feature = "gc", issue = "none")]
+impl<T: ?Sized> fmt::Pointer for HeapGc<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Pointer::fmt(&self.ptr, f)
+    }
+}
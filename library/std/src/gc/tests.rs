@@ -95,3 +95,271 @@ fn test_from_vec() {
 
     assert_eq!(&g[..], [1, 2, 3]);
 }
+
+#[test]
+fn test_executor() {
+    use crate::collections::VecDeque;
+    use crate::future::Future;
+    use crate::sync::atomic::{AtomicUsize, Ordering};
+    use crate::task::{Context, Poll};
+
+    // The queue lives in GC memory, so that the tasks it holds are found by the collector.
+    type Queue = Mutex<VecDeque<Gc<dyn Run>, GcAllocator>>;
+
+    trait Run: Send + Sync {
+        fn run(self: Gc<Self>);
+    }
+
+    struct Task<F> {
+        // Structurally pinned, as every task is made by `Gc::pin`.
+        future: Mutex<Option<F>>,
+        queue: Gc<Queue>,
+    }
+
+    impl<F: Future<Output = ()> + Send + 'static> Run for Task<F> {
+        fn run(self: Gc<Self>) {
+            let waker = Waker::from(self);
+            let mut cx = Context::from_waker(&waker);
+            let mut slot = self.future.lock().unwrap();
+            if let Some(future) = slot.as_mut() {
+                // SAFETY: The task was made by `Gc::pin`, so the future never moves.
+                if unsafe { Pin::new_unchecked(future) }.poll(&mut cx).is_ready() {
+                    *slot = None;
+                }
+            }
+        }
+    }
+
+    impl<F: Future<Output = ()> + Send + 'static> GcWake for Task<F> {
+        fn wake(self: Gc<Self>) {
+            self.queue.lock().unwrap().push_back(self);
+        }
+    }
+
+    struct YieldNow(bool);
+
+    impl Future for YieldNow {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                return Poll::Ready(());
+            }
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+
+    static DONE: AtomicUsize = AtomicUsize::new(0);
+
+    let queue: Gc<Queue> = Gc::new(Mutex::new(VecDeque::new_in(GcAllocator)));
+    for i in 0..10 {
+        let future = async move {
+            for _ in 0..i {
+                YieldNow(false).await;
+            }
+            DONE.fetch_add(1, Ordering::Relaxed);
+        };
+        let task = Gc::pin(Task { future: Mutex::new(Some(future)), queue });
+        // SAFETY: The executor only reaches the future through `Run::run`, which keeps it pinned.
+        queue.lock().unwrap().push_back(unsafe { Pin::into_inner_unchecked(task) });
+    }
+
+    let mut polls = 0;
+    loop {
+        let next = queue.lock().unwrap().pop_front();
+        let Some(task) = next else { break };
+        task.run();
+        polls += 1;
+    }
+    assert_eq!(DONE.load(Ordering::Relaxed), 10);
+    assert_eq!(polls, 10 + (0..10).sum::<usize>());
+}
+
+const SHARED_ITER_MAX: u16 = 100;
+
+fn assert_trusted_len<I: crate::iter::TrustedLen>(_: &I) {}
+
+#[test]
+fn shared_from_iter_normal() {
+    // `Filter` is never `TrustedLen` since we don't know statically how many
+    // elements will be kept.
+    let iter = (0..SHARED_ITER_MAX).filter(|x| x % 2 == 0).map(Box::new);
+
+    // Collecting into a `Vec<T>` or `Gc<[T]>` should make no difference.
+    let vec = iter.clone().collect::<Vec<_>>();
+    let gc = iter.collect::<Gc<[_]>>();
+    assert_eq!(&*vec, &*gc);
+}
+
+#[test]
+fn shared_from_iter_trustedlen_normal() {
+    let iter = (0..SHARED_ITER_MAX).map(Box::new);
+    assert_trusted_len(&iter);
+
+    let vec = iter.clone().collect::<Vec<_>>();
+    let gc = iter.collect::<Gc<[_]>>();
+    assert_eq!(&*vec, &*gc);
+    assert_eq!(mem::size_of::<Box<u16>>() * SHARED_ITER_MAX as usize, mem::size_of_val(&*gc));
+
+    // Try a ZST to make sure it is handled well.
+    let iter = (0..SHARED_ITER_MAX).map(drop);
+    let vec = iter.clone().collect::<Vec<_>>();
+    let gc = iter.collect::<Gc<[_]>>();
+    assert_eq!(&*vec, &*gc);
+    assert_eq!(0, mem::size_of_val(&*gc));
+}
+
+#[test]
+#[should_panic = "I've almost got 99 problems."]
+fn shared_from_iter_trustedlen_panic() {
+    // `size_hint()` is exact, but `next()` panics before the last element.
+    let iter = (0..SHARED_ITER_MAX).map(|val| match val {
+        98 => panic!("I've almost got 99 problems."),
+        _ => Box::new(val),
+    });
+    assert_trusted_len(&iter);
+    let _ = iter.collect::<Gc<[_]>>();
+
+    panic!("I am unreachable.");
+}
+
+#[test]
+fn shared_from_iter_trustedlen_no_fuse() {
+    // `size_hint()` is exact, but the iterator is not fused.
+    struct Iter(crate::vec::IntoIter<Option<Box<u8>>>);
+
+    unsafe impl crate::iter::TrustedLen for Iter {}
+
+    impl Iterator for Iter {
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (2, Some(2))
+        }
+
+        type Item = Box<u8>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.0.next().flatten()
+        }
+    }
+
+    let vec = vec![Some(Box::new(42)), Some(Box::new(24)), None, Some(Box::new(12))];
+    let iter = Iter(vec.into_iter());
+    assert_trusted_len(&iter);
+    assert_eq!(&[Box::new(42), Box::new(24)], &*iter.collect::<Gc<[_]>>());
+}
+
+#[test]
+fn test_from_cow() {
+    use crate::borrow::Cow;
+    use crate::string::String;
+
+    let borrowed: Gc<str> = Gc::from(Cow::Borrowed("eggplant"));
+    let owned: Gc<str> = Gc::from(Cow::<str>::Owned(String::from("eggplant")));
+    assert_eq!(&borrowed[..], "eggplant");
+    assert_eq!(borrowed, owned);
+
+    let slice: Gc<[u8]> = Gc::from(Cow::Borrowed(&[1, 2, 3][..]));
+    assert_eq!(&slice[..], [1, 2, 3]);
+}
+
+#[test]
+fn test_str_to_bytes() {
+    let string: Gc<str> = Gc::from("eggplant");
+    let bytes: Gc<[u8]> = Gc::from(string);
+    assert_eq!(&bytes[..], b"eggplant");
+}
+
+#[test]
+fn test_try_from_slice() {
+    let slice: Gc<[u32]> = Gc::from(vec![1, 2, 3]);
+    let array: Gc<[u32; 3]> = Gc::try_from(slice).unwrap();
+    assert_eq!(*array, [1, 2, 3]);
+    assert!(Gc::<[u32; 2]>::try_from(Gc::<[u32]>::from(vec![1, 2, 3])).is_err());
+}
+
+#[test]
+fn test_from_c_str() {
+    use crate::ffi::{CStr, CString};
+
+    let borrowed: Gc<CStr> = Gc::from(CStr::from_bytes_with_nul(b"eggplant\0").unwrap());
+    let owned: Gc<CStr> = Gc::from(CString::new("eggplant").unwrap());
+    assert_eq!(borrowed.to_bytes(), b"eggplant");
+    assert_eq!(borrowed, owned);
+}
+
+#[test]
+fn test_from_os_str() {
+    use crate::ffi::{OsStr, OsString};
+
+    let borrowed: Gc<OsStr> = Gc::from(OsStr::new("eggplant"));
+    let owned: Gc<OsStr> = Gc::from(OsString::from("eggplant"));
+    assert_eq!(&*borrowed, "eggplant");
+    assert_eq!(borrowed, owned);
+}
+
+#[test]
+fn test_from_path() {
+    use crate::path::{Path, PathBuf};
+
+    let borrowed: Gc<Path> = Gc::from(Path::new("/tmp/eggplant"));
+    let owned: Gc<Path> = Gc::from(PathBuf::from("/tmp/eggplant"));
+    assert_eq!(borrowed.file_name().unwrap(), "eggplant");
+    assert_eq!(borrowed, owned);
+}
+
+#[test]
+fn test_unwrap_or_clone() {
+    use crate::string::String;
+
+    let gc = Gc::new(String::from("eggplant"));
+    let inner = Gc::unwrap_or_clone(gc);
+    assert_eq!(inner, "eggplant");
+    assert_ne!(inner.as_ptr(), gc.as_ptr());
+}
+
+#[test]
+fn test_downcast_send_sync() {
+    use crate::string::String;
+
+    let value: Gc<dyn Any + Send + Sync> = Gc::new(String::from("eggplant"));
+    let value = value.downcast::<u32>().unwrap_err();
+    assert_eq!(*value.downcast::<String>().unwrap(), "eggplant");
+}
+
+#[test]
+fn test_error() {
+    use crate::error::Error;
+    use crate::fmt;
+
+    #[derive(Debug)]
+    struct Inner;
+
+    impl fmt::Display for Inner {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("inner")
+        }
+    }
+
+    impl Error for Inner {}
+
+    #[derive(Debug)]
+    struct Outer(Inner);
+
+    impl fmt::Display for Outer {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("outer")
+        }
+    }
+
+    impl Error for Outer {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    let error: Box<dyn Error> = Box::new(Gc::new(Outer(Inner)));
+    assert_eq!(error.to_string(), "outer");
+    assert_eq!(error.source().unwrap().to_string(), "inner");
+}
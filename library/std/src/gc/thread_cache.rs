@@ -0,0 +1,138 @@
+//! Per-thread free lists for small GC allocations.
+//!
+//! Objects of up to `MAX_SIZE` bytes are handed out from free lists owned by
+//! the allocating thread, which are refilled in batches with `GC_malloc_many`.
+//! This lets the common case of allocating a small `Gc` pop an object without
+//! calling into the collector at all.
+//!
+//! The collector does not scan thread-local storage, so the free lists
+//! themselves live in an uncollectable GC object which thread-local storage
+//! only points to. This keeps the objects on each list alive (and unused by
+//! anyone else) until the thread gives its lists back.
+
+use core::cell::Cell;
+use core::mem;
+use core::ptr;
+
+/// The collector's allocation granule. Every object it hands out is a
+/// multiple of this size.
+const GRANULE: usize = 16;
+const NUM_CLASSES: usize = 4;
+
+/// The largest allocation served from the free lists.
+pub(super) const MAX_SIZE: usize = GRANULE * NUM_CLASSES;
+
+#[repr(C)]
+struct FreeLists {
+    /// The head of the free list for each size class. The objects on a list
+    /// are linked through their first word.
+    heads: [*mut u8; NUM_CLASSES],
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// This thread has not allocated from its free lists yet.
+    Uninit,
+    /// `LISTS` points to this thread's free lists.
+    Active,
+    /// This thread must always allocate through the collector, either because
+    /// it is not registered with the collector or because it is exiting.
+    Disabled,
+}
+
+#[thread_local]
+static STATE: Cell<State> = Cell::new(State::Uninit);
+
+#[thread_local]
+static LISTS: Cell<*mut FreeLists> = Cell::new(ptr::null_mut());
+
+/// Gives this thread's free lists back to the collector when its thread-local
+/// storage is torn down, for threads which did not call [`release`] first.
+struct Teardown;
+
+impl Drop for Teardown {
+    fn drop(&mut self) {
+        release();
+    }
+}
+
+thread_local! {
+    static TEARDOWN: Teardown = const { Teardown };
+}
+
+/// Allocates an object of `size` bytes, where `0 < size <= MAX_SIZE`, from
+/// this thread's free lists. Returns null if the caller must allocate through
+/// the collector instead.
+#[inline]
+pub(super) unsafe fn alloc(size: usize) -> *mut u8 {
+    debug_assert!(size > 0 && size <= MAX_SIZE);
+    match STATE.get() {
+        State::Active => {}
+        State::Uninit => {
+            if !init() {
+                return ptr::null_mut();
+            }
+        }
+        State::Disabled => return ptr::null_mut(),
+    }
+
+    let class = (size - 1) / GRANULE;
+    unsafe {
+        let head = &mut (*LISTS.get()).heads[class];
+        if head.is_null() {
+            *head = refill(class);
+            if head.is_null() {
+                return ptr::null_mut();
+            }
+        }
+        let obj = *head;
+        *head = *(obj as *mut *mut u8);
+        // The collector hands out zeroed memory, so clear the link as well.
+        *(obj as *mut *mut u8) = ptr::null_mut();
+        obj
+    }
+}
+
+#[cold]
+#[inline(never)]
+fn refill(class: usize) -> *mut u8 {
+    unsafe { bdwgc::GC_malloc_many((class + 1) * GRANULE) }
+}
+
+#[cold]
+#[inline(never)]
+fn init() -> bool {
+    // Allocations made while setting up (e.g. when registering the thread-local
+    // destructor) must go through the collector.
+    STATE.set(State::Disabled);
+    if !super::thread_registered() {
+        return false;
+    }
+    let lists = unsafe { bdwgc::GC_malloc_uncollectable(mem::size_of::<FreeLists>()) };
+    if lists.is_null() {
+        return false;
+    }
+    if TEARDOWN.try_with(|_| ()).is_err() {
+        // Thread-local storage is already being torn down.
+        unsafe { bdwgc::GC_free(lists) };
+        return false;
+    }
+    LISTS.set(lists as *mut FreeLists);
+    STATE.set(State::Active);
+    true
+}
+
+/// Gives this thread's free lists back to the collector. Any allocation this
+/// thread makes afterwards goes through the collector.
+///
+/// Threads spawned by std call this just before they exit, while they are
+/// still registered with the collector.
+pub(crate) fn release() {
+    STATE.set(State::Disabled);
+    let lists = LISTS.replace(ptr::null_mut());
+    if !lists.is_null() {
+        // The objects left on the lists are now unreachable, so the next
+        // collection reclaims them.
+        unsafe { bdwgc::GC_free(lists as *mut u8) };
+    }
+}
@@ -359,6 +359,7 @@
 #![feature(str_internals)]
 #![feature(strict_provenance)]
 #![feature(strict_provenance_atomic_ptr)]
+#![feature(trusted_len)]
 // tidy-alphabetical-end
 //
 // Library features (alloc):
@@ -645,6 +646,9 @@ pub mod task {
     #[doc(inline)]
     #[stable(feature = "wake_trait", since = "1.51.0")]
     pub use alloc::task::*;
+
+    #[unstable(feature = "gc", issue = "none")]
+    pub use crate::gc::GcWake;
 }
 
 #[doc = include_str!("../../stdarch/crates/core_arch/src/core_arch_docs.md")]
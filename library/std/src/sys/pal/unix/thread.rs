@@ -108,6 +108,11 @@ extern "C" fn thread_start(main: *mut libc::c_void) -> *mut libc::c_void {
                 debug_assert!(crate::gc::thread_registered());
                 // Finally, let's run some code.
                 Box::from_raw(main as *mut Box<dyn FnOnce()>)();
+
+                // The collector unregisters this thread once we return, so give back its
+                // allocation free lists while it can still do so safely.
+                #[cfg(all(target_thread_local, not(miri), not(feature = "gc-no-thread-cache")))]
+                crate::gc::thread_cache::release();
             }
             ptr::null_mut()
         }
@@ -910,7 +910,8 @@ fn run(self, builder: &Builder<'_>) -> GeneratedTarball {
         copy_src_dirs(
             builder,
             &builder.src,
-            &["library", "src/llvm-project/libunwind"],
+            // `src/bdwgc` is the native source of the `bdwgc` crate, which std depends on.
+            &["library", "src/llvm-project/libunwind", "src/bdwgc"],
             &[
                 // not needed and contains symlinks which rustup currently
                 // chokes on when unpacking.
@@ -312,6 +312,10 @@ pub struct Config {
     pub finalizer_safety_analysis: bool,
     pub premature_finalizer_prevention: bool,
     pub premature_finalizer_prevention_optimize: bool,
+    pub premature_finalizer_prevention_ffi: bool,
+    pub gc_poison_freed: bool,
+    pub gc_write_barriers: bool,
+    pub gc_thread_cache: bool,
 
     // misc
     pub low_priority: bool,
@@ -1148,6 +1152,10 @@ struct Alloy {
         finalizer_safety_analysis: Option<bool> = "finalizer-safety-analysis",
         premature_finalizer_prevention: Option<bool> = "premature-finalizer-prevention",
         premature_finalizer_prevention_optimize: Option<bool> = "premature-finalizer-prevention-optimize",
+        premature_finalizer_prevention_ffi: Option<bool> = "premature-finalizer-prevention-ffi",
+        gc_poison_freed: Option<bool> = "gc-poison-freed",
+        gc_write_barriers: Option<bool> = "gc-write-barriers",
+        gc_thread_cache: Option<bool> = "gc-thread-cache",
     }
 }
 
@@ -1223,6 +1231,10 @@ pub fn default_opts() -> Config {
             finalizer_safety_analysis: true,
             premature_finalizer_prevention: true,
             premature_finalizer_prevention_optimize: true,
+            premature_finalizer_prevention_ffi: false,
+            gc_poison_freed: false,
+            gc_write_barriers: false,
+            gc_thread_cache: true,
 
             ..Default::default()
         }
@@ -1783,6 +1795,10 @@ fn get_table(option: &str) -> Result<TomlConfig, toml::de::Error> {
                 finalizer_safety_analysis,
                 premature_finalizer_prevention,
                 premature_finalizer_prevention_optimize,
+                premature_finalizer_prevention_ffi,
+                gc_poison_freed,
+                gc_write_barriers,
+                gc_thread_cache,
             } = alloy;
 
             set(&mut config.log_stats, log_stats);
@@ -1793,6 +1809,10 @@ fn get_table(option: &str) -> Result<TomlConfig, toml::de::Error> {
                 &mut config.premature_finalizer_prevention_optimize,
                 premature_finalizer_prevention_optimize,
             );
+            set(&mut config.premature_finalizer_prevention_ffi, premature_finalizer_prevention_ffi);
+            set(&mut config.gc_poison_freed, gc_poison_freed);
+            set(&mut config.gc_write_barriers, gc_write_barriers);
+            set(&mut config.gc_thread_cache, gc_thread_cache);
         }
 
         if let Some(llvm) = toml.llvm {
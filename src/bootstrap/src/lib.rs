@@ -756,6 +756,18 @@ fn std_features(&self, target: TargetSelection) -> String {
         if self.config.premature_finalizer_prevention {
             features.push_str(" premature-finalizer-prevention");
         }
+        if self.config.premature_finalizer_prevention_ffi {
+            features.push_str(" premature-finalizer-prevention-ffi");
+        }
+        if self.config.gc_poison_freed {
+            features.push_str(" gc-poison-freed");
+        }
+        if self.config.gc_write_barriers {
+            features.push_str(" gc-write-barriers");
+        }
+        if !self.config.gc_thread_cache {
+            features.push_str(" gc-no-thread-cache");
+        }
         features
     }
 
@@ -782,6 +794,9 @@ fn rustc_features(&self, kind: Kind, target: TargetSelection) -> String {
         if !self.config.finalizer_elision {
             features.push("rustc_no_elision");
         }
+        if self.config.gc_write_barriers {
+            features.push("rustc_gc_write_barriers");
+        }
 
         // If debug logging is on, then we want the default for tracing:
         // https://github.com/tokio-rs/tracing/blob/3dd5c03d907afdf2c39444a29931833335171554/tracing/src/level_filters.rs#L26
@@ -2070,6 +2070,48 @@ pub(crate) fn has_stripped_entries(&self) -> bool {
     }
 }
 
+/// How a type interacts with the garbage collector when it is owned by a `Gc`.
+///
+/// This is shown next to the auto trait implementations of structs, enums and
+/// unions. For generic types, each property is computed for the type's own
+/// generic parameters, knowing only their bounds, so the answers are
+/// conservative: a type which is not finalizer safe or elidable, or which
+/// needs a finalizer, may still have instantiations which are not.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct GcProperties {
+    pub(crate) needs_finalizer: bool,
+    pub(crate) finalizer_safe: bool,
+    pub(crate) drop_method_finalizer_elidable: bool,
+    pub(crate) finalizer_wrapper: Option<FinalizerWrapper>,
+}
+
+/// A wrapper from `std::gc` which changes how `Gc` finalizes its contents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum FinalizerWrapper {
+    NonFinalizable,
+    FinalizeUnchecked,
+}
+
+impl GcProperties {
+    pub(crate) fn of(tcx: TyCtxt<'_>, ty_def_id: DefId) -> Self {
+        let param_env = tcx.param_env(ty_def_id);
+        let ty = tcx.type_of(ty_def_id).instantiate_identity();
+        let finalizer_wrapper = if ty.is_non_finalizable(tcx) {
+            Some(FinalizerWrapper::NonFinalizable)
+        } else if ty.is_finalize_unchecked(tcx) {
+            Some(FinalizerWrapper::FinalizeUnchecked)
+        } else {
+            None
+        };
+        GcProperties {
+            needs_finalizer: ty.needs_finalizer(tcx, param_env),
+            finalizer_safe: ty.is_finalizer_safe(tcx, param_env),
+            drop_method_finalizer_elidable: ty.drop_method_finalizer_elidable(tcx, param_env),
+            finalizer_wrapper,
+        }
+    }
+}
+
 /// This is a more limited form of the standard Struct, different in that
 /// it lacks the things most items have (name, id, parameterization). Found
 /// only as a variant in an enum.
@@ -2045,6 +2045,7 @@ fn init_id_map() -> FxHashMap<Cow<'static, str>, usize> {
     map.insert("blanket-implementations-list".into(), 1);
     map.insert("deref-methods".into(), 1);
     map.insert("layout".into(), 1);
+    map.insert("gc-properties".into(), 1);
     map.insert("aliased-type".into(), 1);
     map
 }
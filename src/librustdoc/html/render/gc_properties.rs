@@ -0,0 +1,30 @@
+use askama::Template;
+
+use rustc_data_structures::captures::Captures;
+use rustc_hir::def_id::DefId;
+
+use std::fmt;
+
+use crate::clean::{FinalizerWrapper, GcProperties};
+use crate::html::format::display_fn;
+use crate::html::render::Context;
+
+#[derive(Template)]
+#[template(path = "gc_properties.html")]
+struct GcPropertiesSection {
+    props: GcProperties,
+    is_generic: bool,
+}
+
+pub(crate) fn document_gc_properties<'a, 'cx: 'a>(
+    cx: &'a Context<'cx>,
+    ty_def_id: DefId,
+) -> impl fmt::Display + 'a + Captures<'cx> {
+    display_fn(move |f| {
+        let tcx = cx.tcx();
+        let props = GcProperties::of(tcx, ty_def_id);
+        let is_generic = tcx.generics_of(ty_def_id).requires_monomorphization(tcx);
+        GcPropertiesSection { props, is_generic }.render_into(f).unwrap();
+        Ok(())
+    })
+}
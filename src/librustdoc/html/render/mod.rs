@@ -29,6 +29,7 @@
 mod tests;
 
 mod context;
+mod gc_properties;
 mod print_item;
 mod sidebar;
 mod span_map;
@@ -13,6 +13,7 @@
 use std::fmt;
 use std::rc::Rc;
 
+use super::gc_properties::document_gc_properties;
 use super::type_layout::document_type_layout;
 use super::{
     collect_paths_for_type, document, ensure_trailing_slash, get_filtered_impls_for_reference,
@@ -102,6 +103,17 @@ fn document<'b>(&'b self) -> impl fmt::Display + Captures<'a> + 'b + Captures<'c
         }
         item_template_methods!($($rest)*);
     };
+    (document_gc_properties $($rest:tt)*) => {
+        fn document_gc_properties<'b>(&'b self) -> impl fmt::Display + Captures<'a> + 'b + Captures<'cx> {
+            display_fn(move |f| {
+                let (item, cx) = self.item_and_mut_cx();
+                let def_id = item.item_id.expect_def_id();
+                let v = document_gc_properties(*cx, def_id);
+                write!(f, "{v}")
+            })
+        }
+        item_template_methods!($($rest)*);
+    };
     (document_type_layout $($rest:tt)*) => {
         fn document_type_layout<'b>(&'b self) -> impl fmt::Display + Captures<'a> + 'b + Captures<'cx> {
             display_fn(move |f| {
@@ -1422,7 +1434,13 @@ struct ItemUnion<'a, 'cx> {
             it: &'a clean::Item,
             s: &'a clean::Union,
         },
-        methods = [document, document_type_layout, render_attributes_in_pre, render_assoc_items]
+        methods = [
+            document,
+            document_gc_properties,
+            document_type_layout,
+            render_attributes_in_pre,
+            render_assoc_items
+        ]
     );
 
     impl<'a, 'cx: 'a> ItemUnion<'a, 'cx> {
@@ -1536,6 +1554,7 @@ fn item_enum(w: &mut Buffer, cx: &mut Context<'_>, it: &clean::Item, e: &clean::
     }
     let def_id = it.item_id.expect_def_id();
     write!(w, "{}", render_assoc_items(cx, it, def_id, AssocItemRender::All));
+    write!(w, "{}", document_gc_properties(cx, def_id));
     write!(w, "{}", document_type_layout(cx, def_id));
 }
 
@@ -1895,6 +1914,7 @@ fn item_struct(w: &mut Buffer, cx: &mut Context<'_>, it: &clean::Item, s: &clean
 
     let def_id = it.item_id.expect_def_id();
     write!(w, "{}", render_assoc_items(cx, it, def_id, AssocItemRender::All));
+    write!(w, "{}", document_gc_properties(cx, def_id));
     write!(w, "{}", document_type_layout(cx, def_id));
 }
 
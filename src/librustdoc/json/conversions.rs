@@ -386,6 +386,7 @@ fn from_tcx(struct_: clean::Struct, tcx: TyCtxt<'_>) -> Self {
             kind,
             generics: generics.into_tcx(tcx),
             impls: Vec::new(), // Added in JsonRenderer::item
+            gc: GcProperties::default(), // Added in JsonRenderer::item
         }
     }
 }
@@ -399,6 +400,27 @@ fn from_tcx(union_: clean::Union, tcx: TyCtxt<'_>) -> Self {
             fields_stripped,
             fields: ids(fields, tcx),
             impls: Vec::new(), // Added in JsonRenderer::item
+            gc: GcProperties::default(), // Added in JsonRenderer::item
+        }
+    }
+}
+
+impl FromWithTcx<clean::GcProperties> for GcProperties {
+    fn from_tcx(props: clean::GcProperties, _tcx: TyCtxt<'_>) -> Self {
+        let clean::GcProperties {
+            needs_finalizer,
+            finalizer_safe,
+            drop_method_finalizer_elidable,
+            finalizer_wrapper,
+        } = props;
+        GcProperties {
+            needs_finalizer,
+            finalizer_safe,
+            drop_method_finalizer_elidable,
+            finalizer_wrapper: finalizer_wrapper.map(|wrapper| match wrapper {
+                clean::FinalizerWrapper::NonFinalizable => FinalizerWrapper::NonFinalizable,
+                clean::FinalizerWrapper::FinalizeUnchecked => FinalizerWrapper::FinalizeUnchecked,
+            }),
         }
     }
 }
@@ -730,6 +752,7 @@ fn from_tcx(enum_: clean::Enum, tcx: TyCtxt<'_>) -> Self {
             variants_stripped,
             variants: ids(variants, tcx),
             impls: Vec::new(), // Added in JsonRenderer::item
+            gc: GcProperties::default(), // Added in JsonRenderer::item
         }
     }
 }
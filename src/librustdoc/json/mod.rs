@@ -98,6 +98,10 @@ fn get_impls(&mut self, id: DefId) -> Vec<types::Id> {
             })
             .unwrap_or_default()
     }
+
+    fn get_gc_properties(&self, id: DefId) -> types::GcProperties {
+        clean::GcProperties::of(self.tcx, id).into_tcx(self.tcx)
+    }
 }
 
 impl<'tcx> FormatRenderer<'tcx> for JsonRenderer<'tcx> {
@@ -159,14 +163,17 @@ fn item(&mut self, item: clean::Item) -> Result<(), Error> {
                 }
                 types::ItemEnum::Struct(ref mut s) => {
                     s.impls = self.get_impls(item_id.expect_def_id());
+                    s.gc = self.get_gc_properties(item_id.expect_def_id());
                     false
                 }
                 types::ItemEnum::Enum(ref mut e) => {
                     e.impls = self.get_impls(item_id.expect_def_id());
+                    e.gc = self.get_gc_properties(item_id.expect_def_id());
                     false
                 }
                 types::ItemEnum::Union(ref mut u) => {
                     u.impls = self.get_impls(item_id.expect_def_id());
+                    u.gc = self.get_gc_properties(item_id.expect_def_id());
                     false
                 }
                 types::ItemEnum::Primitive(ref mut p) => {
@@ -8,7 +8,7 @@
 use std::path::PathBuf;
 
 /// rustdoc format-version.
-pub const FORMAT_VERSION: u32 = 29;
+pub const FORMAT_VERSION: u32 = 30;
 
 /// A `Crate` is the root of the emitted JSON blob. It contains all type/documentation information
 /// about the language items in the local crate, as well as info about external items to allow
@@ -286,6 +286,7 @@ pub struct Union {
     pub fields_stripped: bool,
     pub fields: Vec<Id>,
     pub impls: Vec<Id>,
+    pub gc: GcProperties,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -293,6 +294,38 @@ pub struct Struct {
     pub kind: StructKind,
     pub generics: Generics,
     pub impls: Vec<Id>,
+    pub gc: GcProperties,
+}
+
+/// How a type interacts with the garbage collector when it is owned by a `Gc`.
+///
+/// For generic types, each property is computed for the type's own generic parameters, knowing
+/// only their bounds, so the answers are conservative. `needs_finalizer` may be `true` when some
+/// instantiations need no finalizer, and `finalizer_safe` and `drop_method_finalizer_elidable`
+/// may be `false` when some instantiations are. The opposite answers hold for every
+/// instantiation.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GcProperties {
+    /// Whether a `Gc` which owns this type registers a finalizer.
+    pub needs_finalizer: bool,
+    /// Whether this type implements the `FinalizerSafe` auto trait, i.e. it may be accessed from
+    /// a finalizer.
+    pub finalizer_safe: bool,
+    /// Whether this type implements `DropMethodFinalizerElidable`, i.e. its drop method is
+    /// skipped when it is finalized.
+    pub drop_method_finalizer_elidable: bool,
+    /// Whether this type is one of the `std::gc` wrappers which change how its contents are
+    /// finalized.
+    pub finalizer_wrapper: Option<FinalizerWrapper>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FinalizerWrapper {
+    /// `NonFinalizable<T>`: the wrapped value is never finalized.
+    NonFinalizable,
+    /// `FinalizeUnchecked<T>`: the wrapped value is finalized without finalizer safety analysis.
+    FinalizeUnchecked,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -329,6 +362,7 @@ pub struct Enum {
     pub variants_stripped: bool,
     pub variants: Vec<Id>,
     pub impls: Vec<Id>,
+    pub gc: GcProperties,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -205,6 +205,7 @@ fn get_clap_config() -> ArgMatches {
                             "restriction",
                             "cargo",
                             "nursery",
+                            "gc",
                             "internal",
                         ]),
                     Arg::new("type").long("type").help("What directory the lint belongs in"),
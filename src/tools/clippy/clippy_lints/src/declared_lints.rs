@@ -210,6 +210,10 @@
     crate::functions::TOO_MANY_ARGUMENTS_INFO,
     crate::functions::TOO_MANY_LINES_INFO,
     crate::future_not_send::FUTURE_NOT_SEND_INFO,
+    crate::gc::GC_NON_SYNC_ACROSS_THREADS_INFO,
+    crate::gc::GC_PTR_TO_INT_INFO,
+    crate::gc::RC_WEAK_CYCLE_INFO,
+    crate::gc::UNNECESSARY_FINALIZE_UNCHECKED_INFO,
     crate::if_let_mutex::IF_LET_MUTEX_INFO,
     crate::if_not_else::IF_NOT_ELSE_INFO,
     crate::if_then_some_else_none::IF_THEN_SOME_ELSE_NONE_INFO,
@@ -372,6 +376,7 @@
     crate::methods::FLAT_MAP_OPTION_INFO,
     crate::methods::FORMAT_COLLECT_INFO,
     crate::methods::FROM_ITER_INSTEAD_OF_COLLECT_INFO,
+    crate::methods::GC_CLONE_INFO,
     crate::methods::GET_FIRST_INFO,
     crate::methods::GET_LAST_WITH_LEN_INFO,
     crate::methods::GET_UNWRAP_INFO,
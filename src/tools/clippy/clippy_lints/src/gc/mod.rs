@@ -0,0 +1,157 @@
+mod non_sync_across_threads;
+mod ptr_to_int;
+mod rc_weak_cycle;
+mod unnecessary_finalize_unchecked;
+
+use rustc_hir::{Expr, Item};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::declare_lint_pass;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for structs and enums which point to themselves through both an `Rc` and a
+    /// `rc::Weak`, such as a tree whose children hold a `Weak` reference to their parent.
+    ///
+    /// ### Why is this bad?
+    /// The `Weak` back-edges only exist to stop the `Rc` cycle from leaking. Every access
+    /// through them has to `upgrade()` and handle failure, and getting the direction of an
+    /// edge wrong leaks the whole structure. A `Gc` can point in both directions, as the
+    /// collector reclaims cycles once they become unreachable.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use std::cell::RefCell;
+    /// # use std::rc::{Rc, Weak};
+    /// struct Node {
+    ///     parent: RefCell<Weak<Node>>,
+    ///     children: RefCell<Vec<Rc<Node>>>,
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # #![feature(gc)]
+    /// # use std::cell::RefCell;
+    /// # use std::gc::Gc;
+    /// struct Node {
+    ///     parent: RefCell<Option<Gc<Node>>>,
+    ///     children: RefCell<Vec<Gc<Node>>>,
+    /// }
+    /// ```
+    #[clippy::version = "1.79.0"]
+    pub RC_WEAK_CYCLE,
+    gc,
+    "a type which breaks its own `Rc` cycles with `Weak` back-edges"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for closures passed to `thread::spawn` (or `Builder::spawn` and
+    /// `Scope::spawn`) which capture a `Gc<T>` where `T` is not `Sync`.
+    ///
+    /// ### Why is this bad?
+    /// `Gc` is `Copy`, so the spawning thread keeps its own pointer to the value after
+    /// handing one to the new thread. Unlike `Rc`, `Gc<T>` is `Send` whenever `T` is, so
+    /// nothing stops a `Gc<RefCell<T>>` from crossing threads. That compiles, but leaves
+    /// two threads with unsynchronised access to the same `RefCell`.
+    ///
+    /// ### Example
+    /// ```ignore
+    /// let counter = Gc::new(RefCell::new(0));
+    /// thread::spawn(move || *counter.borrow_mut() += 1);
+    /// ```
+    /// Use instead:
+    /// ```ignore
+    /// let counter = Gc::new(Mutex::new(0));
+    /// thread::spawn(move || *counter.lock().unwrap() += 1);
+    /// ```
+    #[clippy::version = "1.79.0"]
+    pub GC_NON_SYNC_ACROSS_THREADS,
+    gc,
+    "sharing a `Gc<T>` with another thread when `T` is not `Sync`"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for the pointer returned by `Gc::as_ptr` or `Gc::into_raw` being turned into
+    /// an integer, either with an `as` cast or with `addr` or `expose_provenance`.
+    ///
+    /// ### Why is this bad?
+    /// The collector is conservative: it finds live objects by scanning memory for values
+    /// which look like pointers to them. A pointer which has been stored as an integer that
+    /// was later modified (e.g. by tagging it or XOR-ing it with another pointer, as in an
+    /// XOR linked list) is invisible to it, so the object can be freed while it is still
+    /// in use.
+    ///
+    /// ### Known problems
+    /// Using the address as an opaque identity (e.g. for hashing) is fine as long as the
+    /// `Gc` itself is kept alive for as long as the integer is used.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # #![feature(gc)]
+    /// # use std::gc::Gc;
+    /// # let a = Gc::new(1);
+    /// # let b = Gc::new(2);
+    /// let link = Gc::as_ptr(&a) as usize ^ Gc::as_ptr(&b) as usize;
+    /// ```
+    #[clippy::version = "1.79.0"]
+    pub GC_PTR_TO_INT,
+    gc,
+    "converting the pointer inside a `Gc` to an integer, hiding it from the collector"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `FinalizeUnchecked::new(x)` where the type of `x` does not need to be
+    /// finalized at all.
+    ///
+    /// ### Why is this bad?
+    /// `FinalizeUnchecked` opts a value out of finalizer safety analysis, which makes its
+    /// drop method `unsafe` to run on the finalizer thread. When the type has no drop glue
+    /// the collector never runs a finalizer for it, so there is nothing to check, and the
+    /// `unsafe` block only makes readers look for a soundness argument that isn't needed.
+    ///
+    /// This lint only fires when the value provably needs no finalizer. It does not try to
+    /// prove that finalizer safety analysis would have accepted a drop method which does
+    /// exist.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # #![feature(gc)]
+    /// # use std::gc::{FinalizeUnchecked, Gc};
+    /// let x = Gc::new(unsafe { FinalizeUnchecked::new([1u8; 16]) });
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # #![feature(gc)]
+    /// # use std::gc::Gc;
+    /// let x = Gc::new([1u8; 16]);
+    /// ```
+    #[clippy::version = "1.79.0"]
+    pub UNNECESSARY_FINALIZE_UNCHECKED,
+    gc,
+    "wrapping a value which never needs finalizing in `FinalizeUnchecked`"
+}
+
+declare_lint_pass!(GcMisuse => [
+    RC_WEAK_CYCLE,
+    GC_NON_SYNC_ACROSS_THREADS,
+    GC_PTR_TO_INT,
+    UNNECESSARY_FINALIZE_UNCHECKED,
+]);
+
+impl<'tcx> LateLintPass<'tcx> for GcMisuse {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        if in_external_macro(cx.sess(), expr.span) {
+            return;
+        }
+        non_sync_across_threads::check(cx, expr);
+        ptr_to_int::check(cx, expr);
+        unnecessary_finalize_unchecked::check(cx, expr);
+    }
+
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        rc_weak_cycle::check(cx, item);
+    }
+}
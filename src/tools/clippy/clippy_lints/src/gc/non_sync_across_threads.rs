@@ -0,0 +1,47 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::{fn_def_id, match_def_path, paths};
+use rustc_hir::{Expr, ExprKind};
+use rustc_lint::LateContext;
+use rustc_middle::ty::print::with_forced_trimmed_paths;
+use rustc_middle::ty::{self, GenericArgKind};
+
+use super::GC_NON_SYNC_ACROSS_THREADS;
+
+pub(super) fn check<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+    let (ExprKind::Call(_, args) | ExprKind::MethodCall(_, _, args, _)) = expr.kind else {
+        return;
+    };
+    if let Some(did) = fn_def_id(cx, expr)
+        && [&paths::THREAD_SPAWN[..], &paths::THREAD_BUILDER_SPAWN[..], &paths::THREAD_SCOPE_SPAWN[..]]
+            .into_iter()
+            .any(|path| match_def_path(cx, did, path))
+        && let Some(closure) = args.last()
+        && let ty::Closure(_, closure_args) = cx.typeck_results().expr_ty(closure).kind()
+        && let Some((gc_ty, inner_ty)) = closure_args
+            .as_closure()
+            .upvar_tys()
+            .iter()
+            .flat_map(|upvar| upvar.walk())
+            .filter_map(|arg| match arg.unpack() {
+                GenericArgKind::Type(ty) if ty.is_gc(cx.tcx) => match ty.kind() {
+                    ty::Adt(_, args) => Some((ty, args.type_at(0))),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .find(|(_, inner_ty)| !inner_ty.is_sync(cx.tcx, cx.param_env))
+    {
+        span_lint_and_then(
+            cx,
+            GC_NON_SYNC_ACROSS_THREADS,
+            closure.span,
+            with_forced_trimmed_paths!(format!(
+                "sharing a `{gc_ty}` with another thread, but `{inner_ty}` is not `Sync`"
+            )),
+            |diag| {
+                diag.note("`Gc` is `Copy`, so both threads can now access the same value without synchronisation");
+                diag.help("use a `Sync` type such as `Mutex` or `RwLock` inside the `Gc`");
+            },
+        );
+    }
+}
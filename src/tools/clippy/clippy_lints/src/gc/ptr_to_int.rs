@@ -0,0 +1,46 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::fn_def_id;
+use rustc_hir::{Expr, ExprKind};
+use rustc_lint::LateContext;
+use rustc_span::Symbol;
+
+use super::GC_PTR_TO_INT;
+
+pub(super) fn check<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+    let ptr_expr = match expr.kind {
+        ExprKind::Cast(cast_expr, _) if cx.typeck_results().expr_ty(expr).is_integral() => cast_expr,
+        ExprKind::MethodCall(path, receiver, [], _)
+            if matches!(path.ident.as_str(), "addr" | "expose_provenance")
+                && cx.typeck_results().expr_ty(receiver).is_unsafe_ptr() =>
+        {
+            receiver
+        },
+        _ => return,
+    };
+    if let Some(method) = gc_ptr_source(cx, ptr_expr) {
+        span_lint_and_help(
+            cx,
+            GC_PTR_TO_INT,
+            expr.span,
+            format!("converting the pointer returned by `Gc::{method}` to an integer"),
+            None,
+            "the collector cannot see pointers stored as integers, so keep the `Gc` alive while the integer is in use",
+        );
+    }
+}
+
+/// If `expr` is a call to `Gc::as_ptr` or `Gc::into_raw`, possibly followed by pointer-to-pointer
+/// casts, returns the name of the method.
+fn gc_ptr_source(cx: &LateContext<'_>, mut expr: &Expr<'_>) -> Option<Symbol> {
+    while let ExprKind::Cast(cast_expr, _) = expr.peel_blocks().kind
+        && cx.typeck_results().expr_ty(cast_expr).is_unsafe_ptr()
+    {
+        expr = cast_expr;
+    }
+    let did = fn_def_id(cx, expr.peel_blocks())?;
+    let impl_did = cx.tcx.impl_of_method(did)?;
+    let name = cx.tcx.item_name(did);
+    (cx.tcx.type_of(impl_did).instantiate_identity().is_gc(cx.tcx)
+        && matches!(name.as_str(), "as_ptr" | "into_raw"))
+    .then_some(name)
+}
@@ -0,0 +1,61 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::ty::is_type_diagnostic_item;
+use rustc_hir::def_id::DefId;
+use rustc_hir::{FieldDef, Item, ItemKind};
+use rustc_lint::LateContext;
+use rustc_middle::ty::{self, GenericArgKind, Ty};
+use rustc_span::{sym, Symbol};
+
+use super::RC_WEAK_CYCLE;
+
+pub(super) fn check(cx: &LateContext<'_>, item: &Item<'_>) {
+    // The suggested fix needs `Gc`, so only crates which can already use it are linted.
+    if !cx.tcx.features().declared(sym::gc) || item.span.from_expansion() {
+        return;
+    }
+    let fields: Vec<&FieldDef<'_>> = match &item.kind {
+        ItemKind::Struct(data, _) => data.fields().iter().collect(),
+        ItemKind::Enum(def, _) => def.variants.iter().flat_map(|v| v.data.fields()).collect(),
+        _ => return,
+    };
+    let self_did = item.owner_id.to_def_id();
+    let points_to_self = |field: &FieldDef<'_>, ptr| {
+        let field_ty = cx.tcx.type_of(field.def_id).instantiate_identity();
+        contains_ptr_to(cx, field_ty, ptr, self_did)
+    };
+
+    if !fields.iter().any(|field| points_to_self(field, sym::Rc)) {
+        return;
+    }
+    for field in fields.iter().filter(|field| points_to_self(field, sym::RcWeak)) {
+        span_lint_and_help(
+            cx,
+            RC_WEAK_CYCLE,
+            field.ty.span,
+            "`Weak` back-edge used to break an `Rc` cycle",
+            None,
+            "consider using `Gc` for both directions, the collector frees cycles once they are unreachable",
+        );
+    }
+}
+
+/// Whether `ty` contains a `ptr<T>` or `ptr<RefCell<T>>`, where `ptr` is the diagnostic item
+/// `ptr` and `T` is the ADT `target`.
+fn contains_ptr_to<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>, ptr: Symbol, target: DefId) -> bool {
+    ty.walk().any(|arg| {
+        if let GenericArgKind::Type(ty) = arg.unpack()
+            && is_type_diagnostic_item(cx, ty, ptr)
+            && let ty::Adt(_, args) = ty.kind()
+        {
+            let mut pointee = args.type_at(0);
+            if is_type_diagnostic_item(cx, pointee, sym::RefCell)
+                && let ty::Adt(_, args) = pointee.kind()
+            {
+                pointee = args.type_at(0);
+            }
+            matches!(pointee.kind(), ty::Adt(adt, _) if adt.did() == target)
+        } else {
+            false
+        }
+    })
+}
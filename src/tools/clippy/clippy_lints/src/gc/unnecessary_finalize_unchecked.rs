@@ -0,0 +1,29 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::last_path_segment;
+use rustc_hir::{Expr, ExprKind};
+use rustc_lint::LateContext;
+use rustc_middle::ty::print::with_forced_trimmed_paths;
+use rustc_span::sym;
+
+use super::UNNECESSARY_FINALIZE_UNCHECKED;
+
+pub(super) fn check<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+    if let ExprKind::Call(func, [arg]) = expr.kind
+        && let ExprKind::Path(ref func_path) = func.kind
+        && last_path_segment(func_path).ident.name == sym::new
+        && cx.typeck_results().expr_ty(expr).is_finalize_unchecked(cx.tcx)
+        && let arg_ty = cx.typeck_results().expr_ty(arg)
+        && !arg_ty.needs_finalizer(cx.tcx, cx.param_env)
+    {
+        span_lint_and_help(
+            cx,
+            UNNECESSARY_FINALIZE_UNCHECKED,
+            expr.span,
+            with_forced_trimmed_paths!(format!(
+                "`{arg_ty}` never needs finalizing, so wrapping it in `FinalizeUnchecked` has no effect"
+            )),
+            None,
+            "remove the `FinalizeUnchecked` wrapper and the `unsafe` block around it",
+        );
+    }
+}
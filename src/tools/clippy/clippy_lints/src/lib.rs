@@ -153,6 +153,7 @@
 mod from_str_radix_10;
 mod functions;
 mod future_not_send;
+mod gc;
 mod if_let_mutex;
 mod if_not_else;
 mod if_then_some_else_none;
@@ -406,6 +407,7 @@ struct RegistrationGroups {
     cargo: Vec<LintId>,
     complexity: Vec<LintId>,
     correctness: Vec<LintId>,
+    gc: Vec<LintId>,
     nursery: Vec<LintId>,
     pedantic: Vec<LintId>,
     perf: Vec<LintId>,
@@ -423,6 +425,7 @@ fn register(self, store: &mut rustc_lint::LintStore) {
         store.register_group(true, "clippy::cargo", Some("clippy_cargo"), self.cargo);
         store.register_group(true, "clippy::complexity", Some("clippy_complexity"), self.complexity);
         store.register_group(true, "clippy::correctness", Some("clippy_correctness"), self.correctness);
+        store.register_group(true, "clippy::gc", Some("clippy_gc"), self.gc);
         store.register_group(true, "clippy::nursery", Some("clippy_nursery"), self.nursery);
         store.register_group(true, "clippy::pedantic", Some("clippy_pedantic"), self.pedantic);
         store.register_group(true, "clippy::perf", Some("clippy_perf"), self.perf);
@@ -439,6 +442,7 @@ pub(crate) enum LintCategory {
     Cargo,
     Complexity,
     Correctness,
+    Gc,
     Nursery,
     Pedantic,
     Perf,
@@ -461,6 +465,7 @@ fn group(self, groups: &mut RegistrationGroups) -> &mut Vec<LintId> {
             Cargo => &mut groups.cargo,
             Complexity => &mut groups.complexity,
             Correctness => &mut groups.correctness,
+            Gc => &mut groups.gc,
             Nursery => &mut groups.nursery,
             Pedantic => &mut groups.pedantic,
             Perf => &mut groups.perf,
@@ -1133,6 +1138,7 @@ pub fn register_lints(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
     store.register_late_pass(|_| Box::new(zero_repeat_side_effects::ZeroRepeatSideEffects));
     store.register_late_pass(|_| Box::new(manual_unwrap_or_default::ManualUnwrapOrDefault));
     store.register_late_pass(|_| Box::new(integer_division_remainder_used::IntegerDivisionRemainderUsed));
+    store.register_late_pass(|_| Box::new(gc::GcMisuse));
     // add lints here, do not remove this comment, it's used in `new_lint`
 }
 
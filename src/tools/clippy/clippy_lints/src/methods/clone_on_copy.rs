@@ -1,4 +1,5 @@
 use clippy_utils::diagnostics::span_lint_and_sugg;
+use clippy_utils::is_lint_allowed;
 use clippy_utils::source::snippet_with_context;
 use clippy_utils::ty::is_copy;
 use rustc_errors::Applicability;
@@ -9,9 +10,9 @@
 use rustc_middle::ty::{self};
 use rustc_span::symbol::{sym, Symbol};
 
-use super::CLONE_ON_COPY;
+use super::{CLONE_ON_COPY, GC_CLONE};
 
-/// Checks for the `CLONE_ON_COPY` lint.
+/// Checks for the `CLONE_ON_COPY` and `GC_CLONE` lints.
 #[allow(clippy::too_many_lines)]
 pub(super) fn check(
     cx: &LateContext<'_>,
@@ -90,16 +91,21 @@ pub(super) fn check(
             ("try dereferencing it", format!("{}{snip}", "*".repeat(deref_count)))
         };
 
-        span_lint_and_sugg(
-            cx,
-            CLONE_ON_COPY,
-            expr.span,
-            with_forced_trimmed_paths!(format!(
-                "using `clone` on type `{ty}` which implements the `Copy` trait"
-            )),
-            help,
-            sugg,
-            app,
-        );
+        // `gc_clone` is allow-by-default, and a `Gc` is otherwise reported like any other `Copy`
+        // type, so that `#[allow(clippy::clone_on_copy)]` keeps covering it.
+        let (lint, msg) = if ty.is_gc(cx.tcx) && !is_lint_allowed(cx, GC_CLONE, expr.hir_id) {
+            (
+                GC_CLONE,
+                with_forced_trimmed_paths!(format!("using `clone` on `{ty}`, which is `Copy`")),
+            )
+        } else {
+            (
+                CLONE_ON_COPY,
+                with_forced_trimmed_paths!(format!(
+                    "using `clone` on type `{ty}` which implements the `Copy` trait"
+                )),
+            )
+        };
+        span_lint_and_sugg(cx, lint, expr.span, msg, help, sugg, app);
     }
 }
@@ -4082,6 +4082,35 @@
     suspicious,
     "is_empty() called on strings known at compile time"
 }
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for usage of `.clone()` on a `Gc`.
+    ///
+    /// ### Why is this bad?
+    /// `Gc` is `Copy`: cloning one does not bump a reference count like `Rc::clone` does,
+    /// it just copies the pointer. Calling `clone` suggests otherwise, which is misleading
+    /// for readers used to `Rc` and `Arc`.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # #![feature(gc)]
+    /// # use std::gc::Gc;
+    /// let a = Gc::new(42);
+    /// let b = a.clone();
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # #![feature(gc)]
+    /// # use std::gc::Gc;
+    /// let a = Gc::new(42);
+    /// let b = a;
+    /// ```
+    #[clippy::version = "1.79.0"]
+    pub GC_CLONE,
+    gc,
+    "using `clone` on a `Gc`, which is `Copy`"
+}
 pub struct Methods {
     avoid_breaking_exported_api: bool,
     msrv: Msrv,
@@ -4244,6 +4273,7 @@ pub fn new(
     UNNECESSARY_RESULT_MAP_OR_ELSE,
     MANUAL_C_STR_LITERALS,
     UNNECESSARY_GET_THEN_CHECK,
+    GC_CLONE,
 ]);
 
 /// Extracts a method call name, args, and `Span` of the method name.
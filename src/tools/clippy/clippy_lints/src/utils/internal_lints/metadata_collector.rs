@@ -61,6 +61,7 @@
     ("perf", "warn"),
     ("cargo", "allow"),
     ("nursery", "allow"),
+    ("gc", "allow"),
 ];
 /// This prefix is in front of the lint groups in the lint store. The prefix will be trimmed
 /// to only keep the actual lint group in the output.
@@ -92,6 +92,9 @@
 pub const SYMBOL_TO_IDENT_STRING: [&str; 4] = ["rustc_span", "symbol", "Symbol", "to_ident_string"];
 pub const SYM_MODULE: [&str; 3] = ["rustc_span", "symbol", "sym"];
 pub const SYNTAX_CONTEXT: [&str; 3] = ["rustc_span", "hygiene", "SyntaxContext"];
+pub const THREAD_BUILDER_SPAWN: [&str; 4] = ["std", "thread", "Builder", "spawn"];
+pub const THREAD_SCOPE_SPAWN: [&str; 5] = ["std", "thread", "scoped", "Scope", "spawn"];
+pub const THREAD_SPAWN: [&str; 3] = ["std", "thread", "spawn"];
 #[expect(clippy::invalid_paths)] // internal lints do not know about all external crates
 pub const TOKIO_FILE_OPTIONS: [&str; 5] = ["tokio", "fs", "file", "File", "options"];
 #[expect(clippy::invalid_paths)] // internal lints do not know about all external crates
@@ -137,7 +137,7 @@ pub fn declare_clippy_lint(input: TokenStream) -> TokenStream {
         match category.as_str() {
             "correctness" => "Deny",
             "style" | "suspicious" | "complexity" | "perf" => "Warn",
-            "pedantic" | "restriction" | "cargo" | "nursery" | "internal" => "Allow",
+            "pedantic" | "restriction" | "cargo" | "nursery" | "gc" | "internal" => "Allow",
             _ => panic!("unknown category {category}"),
         },
     );
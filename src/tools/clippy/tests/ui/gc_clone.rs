@@ -0,0 +1,28 @@
+#![feature(gc)]
+#![warn(clippy::gc_clone)]
+#![allow(unused)]
+
+use std::gc::Gc;
+
+fn main() {
+    let a = Gc::new(42);
+    let b = a.clone();
+    //~^ ERROR: using `clone` on `Gc<i32>`, which is `Copy`
+
+    let r = &a;
+    let c = r.clone();
+    //~^ ERROR: using `clone` on `Gc<i32>`, which is `Copy`
+
+    // `Clone::clone` on a `Copy` type other than `Gc` is left to `clone_on_copy`.
+    #[allow(clippy::clone_on_copy)]
+    let d = 42.clone();
+}
+
+// While `gc_clone` is allowed, `Gc` is reported by `clone_on_copy` and can be allowed through it.
+#[allow(clippy::gc_clone)]
+fn fallback(a: Gc<i32>) {
+    #[allow(clippy::clone_on_copy)]
+    let b = a.clone();
+    let c = a.clone();
+    //~^ ERROR: using `clone` on type `Gc<i32>` which implements the `Copy` trait
+}
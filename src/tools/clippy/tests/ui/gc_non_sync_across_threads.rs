@@ -0,0 +1,27 @@
+#![feature(gc)]
+#![warn(clippy::gc_non_sync_across_threads)]
+#![allow(unused)]
+
+use std::cell::RefCell;
+use std::gc::Gc;
+use std::sync::Mutex;
+use std::thread;
+
+fn main() {
+    let counter = Gc::new(RefCell::new(0));
+    thread::spawn(move || *counter.borrow_mut() += 1);
+    //~^ ERROR: sharing a `Gc<RefCell<i32>>` with another thread, but `RefCell<i32>` is not `Sync`
+
+    let builder = thread::Builder::new();
+    builder.spawn(move || *counter.borrow_mut() += 1).unwrap();
+    //~^ ERROR: sharing a `Gc<RefCell<i32>>` with another thread, but `RefCell<i32>` is not `Sync`
+
+    thread::scope(|s| {
+        s.spawn(move || *counter.borrow_mut() += 1);
+        //~^ ERROR: sharing a `Gc<RefCell<i32>>` with another thread, but `RefCell<i32>` is not `Sync`
+    });
+
+    // `Mutex` is `Sync`.
+    let counter = Gc::new(Mutex::new(0));
+    thread::spawn(move || *counter.lock().unwrap() += 1);
+}
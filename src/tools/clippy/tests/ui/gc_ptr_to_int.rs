@@ -0,0 +1,26 @@
+#![feature(gc, strict_provenance)]
+#![warn(clippy::gc_ptr_to_int)]
+#![allow(unused)]
+
+use std::gc::Gc;
+
+fn main() {
+    let a = Gc::new(1);
+    let b = Gc::new(2);
+
+    let link = Gc::as_ptr(&a) as usize ^ Gc::as_ptr(&b) as usize;
+    //~^ ERROR: converting the pointer returned by `Gc::as_ptr` to an integer
+    //~| ERROR: converting the pointer returned by `Gc::as_ptr` to an integer
+
+    let _ = Gc::into_raw(a) as *const u8 as usize;
+    //~^ ERROR: converting the pointer returned by `Gc::into_raw` to an integer
+
+    let _ = Gc::as_ptr(&b).addr();
+    //~^ ERROR: converting the pointer returned by `Gc::as_ptr` to an integer
+
+    // Pointer-to-pointer casts keep the reference visible to the collector.
+    let _ = Gc::as_ptr(&b) as *const u8;
+
+    let x = Box::new(3);
+    let _ = &*x as *const i32 as usize;
+}
@@ -0,0 +1,40 @@
+#![feature(gc)]
+#![warn(clippy::rc_weak_cycle)]
+#![allow(unused)]
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+struct Node {
+    parent: RefCell<Weak<Node>>,
+    //~^ ERROR: `Weak` back-edge used to break an `Rc` cycle
+    children: RefCell<Vec<Rc<Node>>>,
+}
+
+struct ListNode {
+    next: Option<Rc<RefCell<ListNode>>>,
+    prev: Option<Weak<RefCell<ListNode>>>,
+    //~^ ERROR: `Weak` back-edge used to break an `Rc` cycle
+}
+
+enum Tree {
+    Leaf { parent: Weak<Tree> },
+    //~^ ERROR: `Weak` back-edge used to break an `Rc` cycle
+    Branch(Vec<Rc<Tree>>),
+}
+
+// No `Rc` pointing back to `Observer`, so the `Weak` isn't breaking a cycle.
+struct Observer {
+    subject: Weak<Subject>,
+}
+
+struct Subject {
+    observers: Vec<Rc<Observer>>,
+}
+
+// An `Rc` to itself without `Weak` back-edges.
+struct Chain {
+    next: Option<Rc<Chain>>,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+// Without `feature(gc)` there is no `Gc` to suggest, so nothing is linted.
+#![warn(clippy::rc_weak_cycle)]
+#![allow(unused)]
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+struct Node {
+    parent: RefCell<Weak<Node>>,
+    children: RefCell<Vec<Rc<Node>>>,
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+#![feature(gc)]
+#![warn(clippy::unnecessary_finalize_unchecked)]
+#![allow(unused)]
+
+use std::gc::{FinalizeUnchecked, Gc};
+
+struct HasDrop(*mut i32);
+
+impl Drop for HasDrop {
+    fn drop(&mut self) {
+        unsafe { *self.0 = 0 };
+    }
+}
+
+fn main() {
+    let _ = Gc::new(unsafe { FinalizeUnchecked::new([1u8; 16]) });
+    //~^ ERROR: `[u8; 16]` never needs finalizing, so wrapping it in `FinalizeUnchecked` has no
+    let _ = Gc::new(unsafe { FinalizeUnchecked::new((1, 'a')) });
+    //~^ ERROR: `(i32, char)` never needs finalizing, so wrapping it in `FinalizeUnchecked` has no
+
+    // FSA rejects the raw pointer dereference in `HasDrop::drop`.
+    let mut x = 1;
+    let _ = Gc::new(unsafe { FinalizeUnchecked::new(HasDrop(&mut x as *mut i32)) });
+}
@@ -119,6 +119,7 @@
                 complexity: true,
                 correctness: true,
                 deprecated: false,
+                gc: true,
                 nursery: true,
                 pedantic: true,
                 perf: true,
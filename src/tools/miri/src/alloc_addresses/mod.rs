@@ -85,6 +85,11 @@ pub fn new(config: &MiriConfig, stack_addr: u64) -> Self {
         }
     }
 
+    /// Whether the given allocation's address has been exposed.
+    pub fn is_exposed(&self, alloc_id: AllocId) -> bool {
+        self.exposed.contains(&alloc_id)
+    }
+
     pub fn remove_unreachable_allocs(&mut self, allocs: &LiveAllocs<'_, '_, '_>) {
         // `exposed` and `int_to_ptr_map` are cleared immediately when an allocation
         // is freed, so `base_addr` is the only one we have to clean up based on the GC.
@@ -546,6 +546,9 @@ fn main() {
             miri_config.cmpxchg_weak_failure_rate = parse_rate(param).unwrap_or_else(|err| {
                 show_error!("-Zmiri-compare-exchange-weak-failure-rate {err}")
             });
+        } else if let Some(param) = arg.strip_prefix("-Zmiri-gc-collection-rate=") {
+            miri_config.gc_collection_rate = parse_rate(param)
+                .unwrap_or_else(|err| show_error!("-Zmiri-gc-collection-rate {err}"));
         } else if let Some(param) = arg.strip_prefix("-Zmiri-preemption-rate=") {
             miri_config.preemption_rate =
                 parse_rate(param).unwrap_or_else(|err| show_error!("-Zmiri-preemption-rate {err}"));
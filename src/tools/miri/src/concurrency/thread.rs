@@ -492,7 +492,7 @@ pub fn get_live_thread_count(&self) -> usize {
     }
 
     /// Has the given thread terminated?
-    fn has_terminated(&self, thread_id: ThreadId) -> bool {
+    pub fn has_terminated(&self, thread_id: ThreadId) -> bool {
         self.threads[thread_id].state == ThreadState::Terminated
     }
 
@@ -924,6 +924,22 @@ fn start_regular_thread(
         Ok(new_thread_id)
     }
 
+    /// Start a thread with an empty stack. All its work is scheduled by `on_stack_empty`, which
+    /// is invoked right away when the thread first gets to run.
+    fn start_internal_thread(
+        &mut self,
+        on_stack_empty: StackEmptyCallback<'mir, 'tcx>,
+    ) -> ThreadId {
+        let this = self.eval_context_mut();
+
+        let new_thread_id = this.machine.threads.create_thread(on_stack_empty);
+        let current_span = this.machine.current_span();
+        if let Some(data_race) = &mut this.machine.data_race {
+            data_race.thread_created(&this.machine.threads, new_thread_id, current_span);
+        }
+        new_thread_id
+    }
+
     #[inline]
     fn detach_thread(
         &mut self,
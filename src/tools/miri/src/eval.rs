@@ -154,6 +154,8 @@ pub struct MiriConfig {
     pub address_reuse_rate: f64,
     /// Probability for address reuse across threads.
     pub address_reuse_cross_thread_rate: f64,
+    /// The probability of a GC allocation triggering a full collection of the GC heap.
+    pub gc_collection_rate: f64,
 }
 
 impl Default for MiriConfig {
@@ -192,6 +194,7 @@ fn default() -> MiriConfig {
             collect_leak_backtraces: true,
             address_reuse_rate: 0.5,
             address_reuse_cross_thread_rate: 0.1,
+            gc_collection_rate: 0.0,
         }
     }
 }
@@ -133,6 +133,9 @@ pub enum MiriMemoryKind {
     Tls,
     /// Memory mapped directly by the program
     Mmap,
+    /// Memory managed by the garbage collector (`GC_malloc` and friends).
+    /// This memory may leak: it is the collector's job to free it.
+    Gc,
 }
 
 impl From<MiriMemoryKind> for MemoryKind {
@@ -148,7 +151,7 @@ fn may_leak(self) -> bool {
         use self::MiriMemoryKind::*;
         match self {
             Rust | Miri | C | WinHeap | WinLocal | Runtime => false,
-            Machine | Global | ExternStatic | Tls | Mmap => true,
+            Machine | Global | ExternStatic | Tls | Mmap | Gc => true,
         }
     }
 }
@@ -159,7 +162,7 @@ fn should_save_allocation_span(self) -> bool {
         use self::MiriMemoryKind::*;
         match self {
             // Heap allocations are fine since the `Allocation` is created immediately.
-            Rust | Miri | C | WinHeap | WinLocal | Mmap => true,
+            Rust | Miri | C | WinHeap | WinLocal | Mmap | Gc => true,
             // Everything else is unclear, let's not show potentially confusing spans.
             Machine | Global | ExternStatic | Tls | Runtime => false,
         }
@@ -181,6 +184,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             ExternStatic => write!(f, "extern static"),
             Tls => write!(f, "thread-local static"),
             Mmap => write!(f, "mmap"),
+            Gc => write!(f, "GC heap"),
         }
     }
 }
@@ -469,6 +473,9 @@ pub struct MiriMachine<'mir, 'tcx> {
     /// The table of directory descriptors.
     pub(crate) dirs: shims::unix::DirTable,
 
+    /// The state of the emulated garbage collector.
+    pub(crate) gc: GcState,
+
     /// This machine's monotone clock.
     pub(crate) clock: Clock,
 
@@ -644,6 +651,7 @@ pub(crate) fn new(config: &MiriConfig, layout_cx: LayoutCx<'tcx, TyCtxt<'tcx>>)
             validate: config.validate,
             fds: FdTable::new(config.mute_stdout_stderr),
             dirs: Default::default(),
+            gc: GcState::new(config),
             layouts,
             threads: ThreadManager::default(),
             static_roots: Vec::new(),
@@ -777,6 +785,7 @@ fn visit_provenance(&self, visit: &mut VisitWith<'_>) {
             cmd_line,
             extern_statics,
             dirs,
+            gc,
             borrow_tracker,
             data_race,
             alloc_addresses,
@@ -820,6 +829,7 @@ fn visit_provenance(&self, visit: &mut VisitWith<'_>) {
         tls.visit_provenance(visit);
         env_vars.visit_provenance(visit);
         dirs.visit_provenance(visit);
+        gc.visit_provenance(visit);
         fds.visit_provenance(visit);
         data_race.visit_provenance(visit);
         borrow_tracker.visit_provenance(visit);
@@ -1238,6 +1248,7 @@ fn before_memory_read(
             machine
                 .emit_diagnostic(NonHaltingDiagnostic::AccessedAlloc(alloc_id, AccessKind::Read));
         }
+        machine.gc.before_memory_access(alloc_id)?;
         if let Some(data_race) = &alloc_extra.data_race {
             data_race.read(alloc_id, range, NaReadType::Read, None, machine)?;
         }
@@ -1262,6 +1273,7 @@ fn before_memory_write(
             machine
                 .emit_diagnostic(NonHaltingDiagnostic::AccessedAlloc(alloc_id, AccessKind::Write));
         }
+        machine.gc.before_memory_access(alloc_id)?;
         if let Some(data_race) = &mut alloc_extra.data_race {
             data_race.write(alloc_id, range, NaWriteType::Write, None, machine)?;
         }
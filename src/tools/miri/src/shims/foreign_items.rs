@@ -475,7 +475,7 @@ fn emulate_foreign_item_inner(
             }
 
             // Rust allocation
-            "__rust_alloc" | "miri_alloc" => {
+            "__rust_alloc" | "__rust_alloc_atomic" | "miri_alloc" => {
                 let default = |this: &mut MiriInterpCx<'mir, 'tcx>| {
                     // Only call `check_shim` when `#[global_allocator]` isn't used. When that
                     // macro is used, we act like no shim exists, so that the exported function can run.
@@ -486,7 +486,7 @@ fn emulate_foreign_item_inner(
                     check_alloc_request(size, align)?;
 
                     let memory_kind = match link_name.as_str() {
-                        "__rust_alloc" => MiriMemoryKind::Rust,
+                        "__rust_alloc" | "__rust_alloc_atomic" => MiriMemoryKind::Rust,
                         "miri_alloc" => MiriMemoryKind::Miri,
                         _ => unreachable!(),
                     };
@@ -501,7 +501,7 @@ fn emulate_foreign_item_inner(
                 };
 
                 match link_name.as_str() {
-                    "__rust_alloc" => return this.emulate_allocator(default),
+                    "__rust_alloc" | "__rust_alloc_atomic" => return this.emulate_allocator(default),
                     "miri_alloc" => {
                         default(this)?;
                         return Ok(EmulateForeignItemResult::NeedsJumping);
@@ -594,6 +594,15 @@ fn emulate_foreign_item_inner(
                     this.write_pointer(new_ptr, dest)
                 });
             }
+            "__rust_realloc_scanned" => {
+                return this.emulate_allocator(|this| {
+                    // See the comment for `__rust_alloc` why `check_shim` is only called in the
+                    // default case. Miri's own allocator has no unscanned memory.
+                    let [ptr, _size, _align] = this.check_shim(abi, Abi::Rust, link_name, args)?;
+                    let ptr = this.read_pointer(ptr)?;
+                    this.write_pointer(ptr, dest)
+                });
+            }
 
             // C memory handling functions
             "memcmp" => {
@@ -955,6 +964,13 @@ fn emulate_foreign_item_inner(
                 );
             }
 
+            // The garbage collector behind `std::gc`
+            name if name.starts_with("GC_") => {
+                return shims::gc::EvalContextExt::emulate_gc_foreign_item(
+                    this, link_name, abi, args, dest,
+                );
+            }
+
             // Platform-specific shims
             _ =>
                 return match this.tcx.sess.target.os.as_ref() {
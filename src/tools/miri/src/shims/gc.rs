@@ -0,0 +1,654 @@
+//! Emulation of the Boehm-Demers-Weiser conservative garbage collector (BDWGC) that backs
+//! `std::gc`.
+//!
+//! The emulated collector traces from everything Miri knows to be a root: the interpreter state
+//! (thread stacks, TLS, ...), the contents of every allocation that is not itself a GC object, and
+//! every GC object whose address has been exposed (the real collector is conservative, so an
+//! integer that looks like a pointer keeps its target alive). Objects from `GC_malloc_atomic` are
+//! never traced, so pointers stored in them do not keep anything alive. Unreachable objects without a
+//! finalizer are freed. Unreachable objects with a finalizer are queued and finalized on a
+//! simulated finalizer thread, which the scheduler interleaves with the rest of the program like
+//! any other thread. Any access to an object after its finalizer has returned is reported as
+//! Undefined Behavior; once the object is collected for good, the usual use-after-free checks
+//! take over.
+//!
+//! Collections happen whenever the program calls `GC_gcollect`, and, if
+//! `-Zmiri-gc-collection-rate` is non-zero, randomly before GC allocations.
+
+use std::collections::VecDeque;
+use std::iter;
+use std::task::Poll;
+
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_span::Symbol;
+use rustc_target::abi::{Align, Size};
+use rustc_target::spec::abi::Abi;
+
+use crate::shims::alloc::EvalContextExt as _;
+use crate::shims::tls::TlsDtorsState;
+use crate::shims::unix::*;
+use crate::*;
+use shims::foreign_items::EmulateForeignItemResult;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum GcObjectState {
+    /// The object is in use, or at least not known to be unreachable yet.
+    Live,
+    /// The object was found to be unreachable and its finalizer is queued or running.
+    Finalizing,
+    /// The finalizer has returned. The object stays allocated until a later collection finds it
+    /// unreachable again, but the program must not touch it anymore.
+    Finalized,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Finalizer {
+    func: Pointer<Option<Provenance>>,
+    client_data: Scalar<Provenance>,
+    /// Whether this finalizer was registered with `GC_register_finalizer` (as opposed to
+    /// `GC_register_finalizer_no_order`), i.e. whether it must run only after all other
+    /// finalizable objects reachable from this object have been finalized.
+    ordered: bool,
+}
+
+#[derive(Debug)]
+struct GcObject {
+    /// The pointer returned by the allocation function. It is used to free the object and is
+    /// what gets passed to the finalizer.
+    base: Pointer<Option<Provenance>>,
+    finalizer: Option<Finalizer>,
+    state: GcObjectState,
+    /// Whether the object was allocated with `GC_malloc_atomic`, which promises that it holds no
+    /// pointers. The collector does not trace through such objects.
+    atomic: bool,
+}
+
+/// The state of the emulated garbage collector.
+#[derive(Debug)]
+pub struct GcState {
+    /// All objects allocated by the collector that have not been freed yet.
+    objects: FxHashMap<AllocId, GcObject>,
+    /// Unreachable objects whose finalizer has not been started yet.
+    pending: VecDeque<AllocId>,
+    /// The thread currently running finalizers, if any.
+    finalizer_thread: Option<ThreadId>,
+    /// The per-thread root sets set up via `GC_init_tls_rootset`.
+    tls_rootsets: FxHashMap<ThreadId, Pointer<Option<Provenance>>>,
+    /// The number of finalizers that ran to completion.
+    finalized_total: u64,
+    /// The number of collections so far.
+    gc_no: u64,
+    /// The probability of a GC allocation triggering a collection first.
+    collection_rate: f64,
+}
+
+impl GcState {
+    pub fn new(config: &MiriConfig) -> Self {
+        GcState {
+            objects: FxHashMap::default(),
+            pending: VecDeque::new(),
+            finalizer_thread: None,
+            tls_rootsets: FxHashMap::default(),
+            finalized_total: 0,
+            gc_no: 0,
+            collection_rate: config.gc_collection_rate,
+        }
+    }
+
+    /// Called before every memory access to check that the program does not touch an object the
+    /// collector has already finalized.
+    #[inline]
+    pub fn before_memory_access<'tcx>(&self, alloc_id: AllocId) -> InterpResult<'tcx> {
+        if self.objects.is_empty() {
+            return Ok(());
+        }
+        if let Some(GcObject { state: GcObjectState::Finalized, .. }) = self.objects.get(&alloc_id)
+        {
+            throw_ub_format!(
+                "memory access to {alloc_id:?}, which has already been finalized by the garbage collector"
+            );
+        }
+        Ok(())
+    }
+}
+
+impl VisitProvenance for GcState {
+    fn visit_provenance(&self, visit: &mut VisitWith<'_>) {
+        let GcState {
+            objects,
+            pending: _,
+            finalizer_thread: _,
+            tls_rootsets,
+            finalized_total: _,
+            gc_no: _,
+            collection_rate: _,
+        } = self;
+
+        for object in objects.values() {
+            // Keep the tag we free the object with, but do not treat the object itself as
+            // reachable: whether it is reachable is the collector's business.
+            let (prov, _offset) = object.base.into_parts();
+            if let Some(Provenance::Concrete { tag, .. }) = prov {
+                visit(None, Some(tag));
+            }
+            if let Some(finalizer) = &object.finalizer {
+                finalizer.func.visit_provenance(visit);
+                finalizer.client_data.visit_provenance(visit);
+            }
+        }
+        for rootset in tls_rootsets.values() {
+            rootset.visit_provenance(visit);
+        }
+    }
+}
+
+impl<'mir, 'tcx: 'mir> EvalContextExt<'mir, 'tcx> for crate::MiriInterpCx<'mir, 'tcx> {}
+pub trait EvalContextExt<'mir, 'tcx: 'mir>: crate::MiriInterpCxExt<'mir, 'tcx> {
+    fn emulate_gc_foreign_item(
+        &mut self,
+        link_name: Symbol,
+        abi: Abi,
+        args: &[OpTy<'tcx, Provenance>],
+        dest: &MPlaceTy<'tcx, Provenance>,
+    ) -> InterpResult<'tcx, EmulateForeignItemResult> {
+        let this = self.eval_context_mut();
+
+        match link_name.as_str() {
+            // Allocation
+            "GC_malloc" => {
+                let [size] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let size = this.read_target_usize(size)?;
+                this.gc_maybe_collect()?;
+                // Unlike `malloc`, BDWGC hands out zeroed memory and never returns NULL for size 0.
+                let res = this.malloc(size.max(1), /*zero_init:*/ true, MiriMemoryKind::Gc)?;
+                this.gc_register_object(res)?;
+                this.write_pointer(res, dest)?;
+            }
+            "GC_malloc_atomic" => {
+                let [size] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let size = this.read_target_usize(size)?;
+                this.gc_maybe_collect()?;
+                // Pointer-free objects are not cleared by BDWGC.
+                let res = this.malloc(size.max(1), /*zero_init:*/ false, MiriMemoryKind::Gc)?;
+                this.gc_register_object(res)?;
+                let (alloc_id, _, _) = this.ptr_get_alloc_id(res)?;
+                this.machine.gc.objects.get_mut(&alloc_id).unwrap().atomic = true;
+                this.write_pointer(res, dest)?;
+            }
+            "GC_posix_memalign" => {
+                let [ret, align, size] =
+                    this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let ret = this.deref_pointer(ret)?;
+                let align = this.read_target_usize(align)?;
+                let size = this.read_target_usize(size)?;
+                if !align.is_power_of_two() || align < this.pointer_size().bytes() {
+                    let einval = this.eval_libc_i32("EINVAL");
+                    this.write_int(einval, dest)?;
+                } else {
+                    this.gc_maybe_collect()?;
+                    let size = size.max(1);
+                    let ptr = this.allocate_ptr(
+                        Size::from_bytes(size),
+                        Align::from_bytes(align).unwrap(),
+                        MiriMemoryKind::Gc.into(),
+                    )?;
+                    // We just allocated this, the access is definitely in-bounds.
+                    this.write_bytes_ptr(
+                        ptr.into(),
+                        iter::repeat(0u8).take(usize::try_from(size).unwrap()),
+                    )
+                    .unwrap();
+                    this.gc_register_object(ptr.into())?;
+                    this.write_pointer(ptr, &ret)?;
+                    this.write_null(dest)?;
+                }
+            }
+            "GC_realloc" => {
+                let [old_ptr, new_size] =
+                    this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let old_ptr = this.read_pointer(old_ptr)?;
+                let new_size = this.read_target_usize(new_size)?;
+                this.gc_maybe_collect()?;
+                let old = this.gc_unregister_object(old_ptr, link_name)?;
+                let res = this.realloc(old_ptr, new_size, MiriMemoryKind::Gc)?;
+                if !this.ptr_is_null(res)? {
+                    this.gc_register_object(res)?;
+                    if let Some(old) = old {
+                        let (alloc_id, _, _) = this.ptr_get_alloc_id(res)?;
+                        let object = this.machine.gc.objects.get_mut(&alloc_id).unwrap();
+                        object.finalizer = old.finalizer;
+                        object.state = old.state;
+                        // Like BDWGC, keep the object kind across reallocation.
+                        object.atomic = old.atomic;
+                    }
+                }
+                this.write_pointer(res, dest)?;
+            }
+            "GC_free" => {
+                let [ptr] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let ptr = this.read_pointer(ptr)?;
+                if !this.ptr_is_null(ptr)? {
+                    this.gc_unregister_object(ptr, link_name)?;
+                    this.free(ptr, MiriMemoryKind::Gc)?;
+                }
+            }
+            "GC_base" => {
+                let [ptr] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let ptr = this.read_pointer(ptr)?;
+                let base = match this.ptr_try_get_alloc_id(ptr) {
+                    Ok((alloc_id, offset, _)) if this.machine.gc.objects.contains_key(&alloc_id) =>
+                        ptr.wrapping_signed_offset(-i64::try_from(offset.bytes()).unwrap(), this),
+                    _ => Pointer::null(),
+                };
+                this.write_pointer(base, dest)?;
+            }
+            "GC_get_kind_and_size" => {
+                let [ptr, psize] =
+                    this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let ptr = this.read_pointer(ptr)?;
+                let psize = this.read_pointer(psize)?;
+                let (alloc_id, _, _) = this.ptr_get_alloc_id(ptr)?;
+                let Some(object) = this.machine.gc.objects.get(&alloc_id) else {
+                    throw_ub_format!(
+                        "`GC_get_kind_and_size` called on a pointer which is not a GC object"
+                    );
+                };
+                // BDWGC's `PTRFREE` and `NORMAL` kinds.
+                let kind = if object.atomic { 0 } else { 1 };
+                if !this.ptr_is_null(psize)? {
+                    let (size, _, _) = this.get_alloc_info(alloc_id);
+                    let psize = this.ptr_to_mplace(psize, this.machine.layouts.usize);
+                    this.write_scalar(Scalar::from_target_usize(size.bytes(), this), &psize)?;
+                }
+                this.write_int(kind, dest)?;
+            }
+
+            // Finalization
+            "GC_register_finalizer" | "GC_register_finalizer_no_order" => {
+                let [ptr, func, client_data, old_func, old_client_data] =
+                    this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let ptr = this.read_pointer(ptr)?;
+                let func = this.read_pointer(func)?;
+                let client_data = this.read_scalar(client_data)?;
+                let old_func = this.read_pointer(old_func)?;
+                let old_client_data = this.read_pointer(old_client_data)?;
+
+                let alloc_id = match this.ptr_try_get_alloc_id(ptr) {
+                    Ok((alloc_id, offset, _))
+                        if offset == Size::ZERO
+                            && this.machine.gc.objects.contains_key(&alloc_id) =>
+                        alloc_id,
+                    _ =>
+                        throw_ub_format!(
+                            "`{link_name}` called on {ptr:?}, which is not the start of a GC allocation"
+                        ),
+                };
+                let new = (!this.ptr_is_null(func)?).then_some(Finalizer {
+                    func,
+                    client_data,
+                    ordered: link_name.as_str() == "GC_register_finalizer",
+                });
+                let old = std::mem::replace(
+                    &mut this.machine.gc.objects.get_mut(&alloc_id).unwrap().finalizer,
+                    new,
+                );
+                if !this.ptr_is_null(old_func)? {
+                    let old_func_place =
+                        this.ptr_to_mplace(old_func, this.machine.layouts.mut_raw_ptr);
+                    match old {
+                        Some(old) => this.write_pointer(old.func, &old_func_place)?,
+                        None => this.write_null(&old_func_place)?,
+                    }
+                }
+                if !this.ptr_is_null(old_client_data)? {
+                    let old_client_data_place =
+                        this.ptr_to_mplace(old_client_data, this.machine.layouts.mut_raw_ptr);
+                    match old {
+                        Some(old) => this.write_scalar(old.client_data, &old_client_data_place)?,
+                        None => this.write_null(&old_client_data_place)?,
+                    }
+                }
+            }
+            "GC_gcollect" => {
+                let [] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                this.gc_collect()?;
+            }
+            "GC_keep_alive" => {
+                // Keeping the argument in a live local until here is all this needs to do.
+                let [ptr] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                this.read_pointer(ptr)?;
+            }
+
+            // Statistics
+            "GC_finalized_total" => {
+                let [] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                this.write_scalar(Scalar::from_u64(this.machine.gc.finalized_total), dest)?;
+            }
+            "GC_get_gc_no" => {
+                let [] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                this.write_scalar(Scalar::from_u64(this.machine.gc.gc_no), dest)?;
+            }
+            "GC_get_prof_stats" => {
+                let [stats, stats_sz] =
+                    this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let stats = this.read_pointer(stats)?;
+                let stats_sz = this.read_target_usize(stats_sz)?;
+                // Miri does not track any of these numbers except for the collection count,
+                // which is the 7th word of `GC_prof_stats_s`.
+                this.write_bytes_ptr(stats, iter::repeat(0u8).take(stats_sz.try_into().unwrap()))?;
+                let word = this.pointer_size();
+                let gc_no_offset = word * 6;
+                if stats_sz >= (gc_no_offset + word).bytes() {
+                    let gc_no = Scalar::from_target_usize(this.machine.gc.gc_no, this);
+                    this.write_scalar(
+                        gc_no,
+                        &this.ptr_to_mplace(
+                            stats.offset(gc_no_offset, this)?,
+                            this.machine.layouts.usize,
+                        ),
+                    )?;
+                }
+                this.write_scalar(Scalar::from_target_usize(stats_sz, this), dest)?;
+            }
+
+            // Setup
+            "GC_init" => {
+                let [] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+            }
+            "GC_set_markers_count" => {
+                let [_count] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+            }
+            "GC_set_warn_proc" => {
+                // Miri's collector never warns.
+                let [_proc] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+            }
+
+            // Threads. Every thread Miri knows about is registered with the collector.
+            "GC_thread_is_registered" => {
+                let [] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                this.write_scalar(Scalar::from_u32(1), dest)?;
+            }
+            "GC_pthread_create" if this.target_os_is_unix() => {
+                let [thread, attr, start, arg] =
+                    this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let result = this.pthread_create(thread, attr, start, arg)?;
+                this.write_scalar(Scalar::from_i32(result), dest)?;
+            }
+            "GC_pthread_join" if this.target_os_is_unix() => {
+                let [thread, retval] =
+                    this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let result = this.pthread_join(thread, retval)?;
+                this.write_scalar(Scalar::from_i32(result), dest)?;
+            }
+            "GC_pthread_detach" if this.target_os_is_unix() => {
+                let [thread] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let result = this.pthread_detach(thread)?;
+                this.write_scalar(Scalar::from_i32(result), dest)?;
+            }
+            "GC_tls_rootset" => {
+                let [] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let active_thread = this.get_active_thread();
+                let rootset = this
+                    .machine
+                    .gc
+                    .tls_rootsets
+                    .get(&active_thread)
+                    .copied()
+                    .unwrap_or(Pointer::null());
+                this.write_pointer(rootset, dest)?;
+            }
+            "GC_init_tls_rootset" => {
+                let [rootset] = this.check_shim(abi, Abi::C { unwind: false }, link_name, args)?;
+                let rootset = this.read_pointer(rootset)?;
+                let active_thread = this.get_active_thread();
+                this.machine.gc.tls_rootsets.insert(active_thread, rootset);
+            }
+
+            _ => return Ok(EmulateForeignItemResult::NotSupported),
+        }
+
+        Ok(EmulateForeignItemResult::NeedsJumping)
+    }
+}
+
+impl<'mir, 'tcx: 'mir> EvalContextExtPriv<'mir, 'tcx> for crate::MiriInterpCx<'mir, 'tcx> {}
+trait EvalContextExtPriv<'mir, 'tcx: 'mir>: crate::MiriInterpCxExt<'mir, 'tcx> {
+    /// Start tracking a fresh GC allocation.
+    fn gc_register_object(&mut self, base: Pointer<Option<Provenance>>) -> InterpResult<'tcx> {
+        let this = self.eval_context_mut();
+        let (alloc_id, _, _) = this.ptr_get_alloc_id(base)?;
+        this.machine
+            .gc
+            .objects
+            .insert(alloc_id, GcObject {
+                base,
+                finalizer: None,
+                state: GcObjectState::Live,
+                atomic: false,
+            });
+        Ok(())
+    }
+
+    /// Stop tracking the GC allocation `ptr` points to, because the program is about to free or
+    /// reallocate it. Returns `None` if `ptr` is null.
+    fn gc_unregister_object(
+        &mut self,
+        ptr: Pointer<Option<Provenance>>,
+        link_name: Symbol,
+    ) -> InterpResult<'tcx, Option<GcObject>> {
+        let this = self.eval_context_mut();
+        if this.ptr_is_null(ptr)? {
+            return Ok(None);
+        }
+        let (alloc_id, _, _) = this.ptr_get_alloc_id(ptr)?;
+        let Some(object) = this.machine.gc.objects.remove(&alloc_id) else {
+            throw_ub_format!("`{link_name}` called on {ptr:?}, which is not a GC allocation");
+        };
+        this.machine.gc.pending.retain(|id| *id != alloc_id);
+        Ok(Some(object))
+    }
+
+    /// Collect with the probability configured by `-Zmiri-gc-collection-rate`.
+    fn gc_maybe_collect(&mut self) -> InterpResult<'tcx> {
+        let this = self.eval_context_mut();
+        use rand::Rng as _;
+
+        let rate = this.machine.gc.collection_rate;
+        if rate > 0.0 && this.machine.rng.get_mut().gen_bool(rate) {
+            this.gc_collect()?;
+        }
+        Ok(())
+    }
+
+    /// Run a full collection: free unreachable objects and queue the finalizers of unreachable
+    /// finalizable objects.
+    fn gc_collect(&mut self) -> InterpResult<'tcx> {
+        let this = self.eval_context_mut();
+        this.machine.gc.gc_no = this.machine.gc.gc_no.saturating_add(1);
+        if this.machine.gc.objects.is_empty() {
+            return Ok(());
+        }
+
+        // The real collector stops the world, which synchronizes the collecting thread with
+        // every other thread. This makes sure that freeing and finalizing objects does not race
+        // with the accesses that made them unreachable.
+        this.gc_stop_the_world();
+
+        // Mark everything reachable from the roots.
+        let mut roots = Vec::new();
+        this.machine.visit_provenance(&mut |id, _| roots.extend(id));
+        this.memory.alloc_map().iter(|it| {
+            for (_id, (kind, alloc)) in it {
+                if !matches!(kind, MemoryKind::Machine(MiriMemoryKind::Gc)) {
+                    alloc.visit_provenance(&mut |id, _| roots.extend(id));
+                }
+            }
+        });
+        {
+            let gc = &this.machine.gc;
+            let alloc_addresses = this.machine.alloc_addresses.borrow();
+            for (id, object) in &gc.objects {
+                // Objects awaiting finalization have been resurrected until their finalizer
+                // returns, and an exposed address may be hiding in any integer.
+                if object.state == GcObjectState::Finalizing || alloc_addresses.is_exposed(*id) {
+                    roots.push(*id);
+                }
+            }
+        }
+        let mut marked = FxHashSet::default();
+        this.gc_mark(&mut marked, roots)?;
+
+        // Decide which unreachable objects get finalized. An object with an ordered finalizer
+        // keeps every finalizable object it can reach from being finalized in this cycle.
+        let mut finalizable: Vec<AllocId> = this
+            .machine
+            .gc
+            .objects
+            .iter()
+            .filter(|(id, object)| !marked.contains(*id) && object.finalizer.is_some())
+            .map(|(id, _)| *id)
+            .collect();
+        finalizable.sort();
+        let mut reachable_from_ordered = FxHashSet::default();
+        for id in &finalizable {
+            let object = &this.machine.gc.objects[id];
+            if object.finalizer.unwrap().ordered && !object.atomic {
+                let mut referents = Vec::new();
+                this.get_alloc_raw(*id)?.visit_provenance(&mut |id, _| referents.extend(id));
+                this.gc_mark(&mut reachable_from_ordered, referents)?;
+            }
+        }
+        for id in &finalizable {
+            if !reachable_from_ordered.contains(id) {
+                let object = this.machine.gc.objects.get_mut(id).unwrap();
+                object.state = GcObjectState::Finalizing;
+                this.machine.gc.pending.push_back(*id);
+            }
+        }
+        // Everything reachable from an object with a finalizer survives this cycle.
+        this.gc_mark(&mut marked, finalizable)?;
+
+        // Free everything else.
+        let mut dead: Vec<AllocId> =
+            this.machine.gc.objects.keys().filter(|id| !marked.contains(*id)).copied().collect();
+        dead.sort();
+        for id in dead {
+            let object = this.machine.gc.objects.remove(&id).unwrap();
+            this.deallocate_ptr(object.base, None, MiriMemoryKind::Gc.into())?;
+        }
+
+        if !this.machine.gc.pending.is_empty() {
+            this.gc_start_finalizer_thread()?;
+        }
+        Ok(())
+    }
+
+    /// Mark all GC objects reachable from `worklist`.
+    fn gc_mark(
+        &self,
+        marked: &mut FxHashSet<AllocId>,
+        mut worklist: Vec<AllocId>,
+    ) -> InterpResult<'tcx> {
+        let this = self.eval_context_ref();
+        while let Some(id) = worklist.pop() {
+            let Some(object) = this.machine.gc.objects.get(&id) else {
+                continue;
+            };
+            if !marked.insert(id) || object.atomic {
+                continue;
+            }
+            this.get_alloc_raw(id)?.visit_provenance(&mut |id, _| worklist.extend(id));
+        }
+        Ok(())
+    }
+
+    /// Make the active thread acquire the clocks of all other threads.
+    fn gc_stop_the_world(&mut self) {
+        let this = self.eval_context_mut();
+        let Some(data_race) = &this.machine.data_race else {
+            return;
+        };
+        let active_thread = this.machine.threads.get_active_thread_id();
+        let current_span = this.machine.current_span();
+        for (id, _stack) in this.machine.threads.all_stacks() {
+            if id != active_thread && !this.machine.threads.has_terminated(id) {
+                let clock = data_race.release_clock(id, current_span).clone();
+                data_race.acquire_clock(&clock, active_thread);
+            }
+        }
+        if let Some(finalizer_thread) = this.machine.gc.finalizer_thread {
+            let clock = data_race.release_clock(active_thread, current_span).clone();
+            data_race.acquire_clock(&clock, finalizer_thread);
+        }
+    }
+
+    /// Make sure there is a finalizer thread to drain the pending finalizers.
+    fn gc_start_finalizer_thread(&mut self) -> InterpResult<'tcx> {
+        let this = self.eval_context_mut();
+        if this.machine.gc.finalizer_thread.is_some() {
+            return Ok(());
+        }
+        let thread = this.start_internal_thread({
+            let mut running: Option<AllocId> = None;
+            let mut tls_dtors: Option<TlsDtorsState<'tcx>> = None;
+            Box::new(move |this: &mut MiriInterpCx<'mir, 'tcx>| {
+                if let Some(tls_dtors) = &mut tls_dtors {
+                    // All finalizers ran, this thread is on its way out.
+                    return tls_dtors.on_stack_empty(this);
+                }
+                match this.gc_run_next_finalizer(&mut running)? {
+                    Poll::Pending => Ok(Poll::Pending),
+                    Poll::Ready(()) => {
+                        // Finalizers queued from now on need a new thread.
+                        this.machine.gc.finalizer_thread = None;
+                        tls_dtors.insert(TlsDtorsState::default()).on_stack_empty(this)
+                    }
+                }
+            })
+        });
+        this.machine.threads.set_thread_name(thread, b"gc-finalizer".to_vec());
+        this.detach_thread(thread, /*allow_terminated_joined*/ false)?;
+        this.machine.gc.finalizer_thread = Some(thread);
+        Ok(())
+    }
+
+    /// Called by the finalizer thread whenever its stack is empty: mark the object whose
+    /// finalizer just returned as finalized, and push a frame for the next finalizer.
+    fn gc_run_next_finalizer(
+        &mut self,
+        running: &mut Option<AllocId>,
+    ) -> InterpResult<'tcx, Poll<()>> {
+        let this = self.eval_context_mut();
+        if let Some(id) = running.take() {
+            // The object may have been freed explicitly in the meantime. If the finalizer
+            // registered a new finalizer, the object is back to normal.
+            if let Some(object) = this.machine.gc.objects.get_mut(&id) {
+                object.state = if object.finalizer.is_some() {
+                    GcObjectState::Live
+                } else {
+                    GcObjectState::Finalized
+                };
+            }
+            this.machine.gc.finalized_total = this.machine.gc.finalized_total.saturating_add(1);
+        }
+        let Some(id) = this.machine.gc.pending.pop_front() else {
+            return Ok(Poll::Ready(()));
+        };
+        // A finalizer runs at most once, unless the finalizer registers itself again.
+        let object = this.machine.gc.objects.get_mut(&id).unwrap();
+        let finalizer = object.finalizer.take().unwrap();
+        let base = object.base;
+        trace!("Running GC finalizer for {:?}", id);
+
+        let instance = this.get_ptr_fn(finalizer.func)?.as_instance()?;
+        this.call_function(
+            instance,
+            Abi::C { unwind: false },
+            &[Scalar::from_maybe_pointer(base, this).into(), finalizer.client_data.into()],
+            None,
+            StackPopCleanup::Root { cleanup: true },
+        )?;
+        *running = Some(id);
+        Ok(Poll::Pending)
+    }
+}
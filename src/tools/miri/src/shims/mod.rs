@@ -5,6 +5,7 @@
 #[cfg(target_os = "linux")]
 pub mod ffi_support;
 pub mod foreign_items;
+pub mod gc;
 pub mod intrinsics;
 pub mod unix;
 pub mod windows;
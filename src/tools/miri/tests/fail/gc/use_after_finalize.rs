@@ -0,0 +1,35 @@
+//@compile-flags: -Zmiri-preemption-rate=0
+#![feature(gc)]
+
+use std::gc::{FinalizeUnchecked, Gc, GcAllocator};
+use std::thread;
+
+struct Inner(usize);
+
+impl Drop for Inner {
+    fn drop(&mut self) {}
+}
+
+struct Outer(Gc<FinalizeUnchecked<Inner>>);
+
+impl Drop for Outer {
+    fn drop(&mut self) {
+        // Both objects became unreachable in the same collection, and `Gc` finalizers run in no
+        // particular order: `Inner` has already been finalized.
+        let inner: &Inner = &self.0;
+        let _val = inner.0; //~ ERROR: has already been finalized
+    }
+}
+
+fn make_garbage() {
+    let inner = unsafe { Gc::new(FinalizeUnchecked::new(Inner(42))) };
+    let _outer = unsafe { Gc::new(FinalizeUnchecked::new(Outer(inner))) };
+}
+
+fn main() {
+    make_garbage();
+    GcAllocator::force_gc();
+    for _ in 0..10 {
+        thread::yield_now();
+    }
+}
@@ -0,0 +1,42 @@
+//@compile-flags: -Zmiri-preemption-rate=0
+#![feature(gc)]
+
+use std::gc::{Gc, GcAllocator};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+static FINALIZED: AtomicUsize = AtomicUsize::new(0);
+
+struct Finalizable(usize);
+
+impl Drop for Finalizable {
+    fn drop(&mut self) {
+        FINALIZED.fetch_add(self.0, Ordering::SeqCst);
+    }
+}
+
+fn make_garbage() {
+    for _ in 0..3 {
+        let _gc = Gc::new(Finalizable(1));
+    }
+}
+
+fn main() {
+    let kept = Gc::new(Finalizable(100));
+    let kept_indirectly = Gc::new(Gc::new(Finalizable(1000)));
+    make_garbage();
+
+    GcAllocator::force_gc();
+    // Give the finalizer thread a chance to run.
+    while FINALIZED.load(Ordering::SeqCst) < 3 {
+        thread::yield_now();
+    }
+    for _ in 0..10 {
+        thread::yield_now();
+    }
+
+    // Only the unreachable objects were finalized, and the others are still usable.
+    assert_eq!(FINALIZED.load(Ordering::SeqCst), 3);
+    assert_eq!(kept.0, 100);
+    assert_eq!(kept_indirectly.0, 1000);
+}
@@ -0,0 +1,32 @@
+#![feature(gc)]
+
+use std::gc::{Gc, GcAllocator};
+use std::mem;
+
+#[global_allocator]
+static A: GcAllocator = GcAllocator;
+
+fn main() {
+    // Pointer-free buffers come from `GC_malloc_atomic`.
+    let mut v: Vec<u32> = (0..16).collect();
+    v.extend(16..64);
+    GcAllocator::force_gc();
+    assert_eq!(v.iter().sum::<u32>(), 63 * 64 / 2);
+
+    let b = Box::new([7u8; 32]);
+    GcAllocator::force_gc();
+    assert!(b.iter().all(|x| *x == 7));
+
+    // Collecting into a buffer that holds `Gc`s must not reuse the unscanned source buffer.
+    let gcs: Vec<Gc<u32>> = v.into_iter().map(Gc::new).collect();
+    GcAllocator::force_gc();
+    assert_eq!(gcs.iter().map(|g| **g).sum::<u32>(), 63 * 64 / 2);
+
+    // Reusing an unscanned buffer for pointers moves it into scanned memory.
+    let mut floats = mem::ManuallyDrop::new(Vec::<f64>::with_capacity(16));
+    let (ptr, cap) = (floats.as_mut_ptr(), floats.capacity());
+    let mut ptrs = unsafe { Vec::<*const u32>::from_raw_parts(ptr as *mut *const u32, 0, cap) };
+    ptrs.extend((0..16).map(|i| Gc::as_ptr(&Gc::new(i))));
+    GcAllocator::force_gc();
+    assert_eq!(ptrs.iter().map(|p| unsafe { **p }).sum::<u32>(), 15 * 16 / 2);
+}
@@ -0,0 +1,47 @@
+//@ compile-flags: -O -C panic=abort
+//@ only-x86_64
+
+#![crate_type = "lib"]
+#![feature(gc)]
+
+use std::gc::Gc;
+
+pub struct Node {
+    value: u64,
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {}
+}
+
+extern "Rust" {
+    fn opaque(x: u64);
+}
+
+// The premature finalization barrier belongs after the last use of the `Gc`,
+// not at the end of its scope.
+
+// CHECK-LABEL: @barrier_before_loop
+#[no_mangle]
+pub fn barrier_before_loop(node: Gc<Node>, n: u64) {
+    // CHECK: call void asm sideeffect "", "r"(ptr %node)
+    // CHECK-NOT: asm sideeffect
+    // CHECK: call void @opaque
+    // CHECK-NOT: asm sideeffect
+    // CHECK: ret void
+    let value = node.value;
+    for i in 0..n {
+        unsafe { opaque(value + i) }
+    }
+}
+
+// CHECK-LABEL: @barrier_after_borrow
+#[no_mangle]
+pub fn barrier_after_borrow(node: Gc<Node>, n: u64) {
+    // CHECK: call void @opaque
+    // CHECK: call void asm sideeffect "", "r"(ptr %node)
+    let value = &node.value;
+    for i in 0..n {
+        unsafe { opaque(*value + i) }
+    }
+}
@@ -0,0 +1,21 @@
+//@ compile-flags: -O
+//@ only-x86_64
+
+#![crate_type = "lib"]
+#![feature(core_intrinsics)]
+#![feature(gc)]
+
+use std::intrinsics::gc_keep_alive;
+
+// The barrier must be an empty asm block which uses the pointer in a register,
+// without clobbering memory or calling into the collector.
+
+// CHECK-LABEL: @keep_alive
+#[no_mangle]
+pub fn keep_alive(ptr: *const u8) {
+    // CHECK: call void asm sideeffect "", "r"(ptr %ptr)
+    // CHECK-NOT: GC_keep_alive
+    // CHECK-NOT: ~{memory}
+    gc_keep_alive(ptr);
+    // CHECK: ret void
+}
@@ -0,0 +1,30 @@
+#![feature(gc)]
+
+use std::gc::Gc;
+
+// @is "$.index[*][?(@.name=='Plain')].inner.struct.gc.needs_finalizer" false
+// @is "$.index[*][?(@.name=='Plain')].inner.struct.gc.finalizer_safe" true
+// @is "$.index[*][?(@.name=='Plain')].inner.struct.gc.drop_method_finalizer_elidable" false
+// @is "$.index[*][?(@.name=='Plain')].inner.struct.gc.finalizer_wrapper" null
+pub struct Plain(pub u8);
+
+// @is "$.index[*][?(@.name=='HasDrop')].inner.struct.gc.needs_finalizer" true
+pub struct HasDrop;
+
+impl Drop for HasDrop {
+    fn drop(&mut self) {}
+}
+
+// @is "$.index[*][?(@.name=='HoldsGc')].inner.struct.gc.finalizer_safe" false
+pub struct HoldsGc(pub Gc<u8>);
+
+// @is "$.index[*][?(@.name=='HoldsRef')].inner.enum.gc.finalizer_safe" false
+pub enum HoldsRef<'a> {
+    A(&'a u8),
+}
+
+// @is "$.index[*][?(@.name=='Bits')].inner.union.gc.needs_finalizer" false
+pub union Bits {
+    pub a: u8,
+    pub b: i8,
+}
@@ -0,0 +1,48 @@
+#![crate_name = "foo"]
+#![feature(gc)]
+
+use std::gc::Gc;
+
+// @has foo/struct.Plain.html
+// @has - '//*[@id="gc-properties"]/a[@href="#gc-properties"]' ''
+// @hasraw - 'No finalizer needed:'
+// @hasraw - '<strong><code>FinalizerSafe</code>:</strong>'
+// @!hasraw - 'DropMethodFinalizerElidable'
+pub struct Plain(pub u8);
+
+// @has foo/struct.HasDrop.html
+// @hasraw - 'Needs a finalizer:'
+pub struct HasDrop;
+
+impl Drop for HasDrop {
+    fn drop(&mut self) {}
+}
+
+// @has foo/struct.HoldsGc.html
+// @hasraw - 'Not <code>FinalizerSafe</code>:'
+pub struct HoldsGc(pub Gc<u8>);
+
+// @has foo/enum.HoldsRef.html
+// @hasraw - 'Not <code>FinalizerSafe</code>:'
+pub enum HoldsRef<'a> {
+    A(&'a u8),
+}
+
+// @has foo/union.Bits.html
+// @has - '//*[@id="gc-properties"]' 'GC Properties'
+pub union Bits {
+    pub a: u8,
+    pub b: i8,
+}
+
+// @has foo/struct.Generic.html
+// @hasraw - 'May need a finalizer:'
+// @hasraw - 'Not always <code>FinalizerSafe</code>:'
+// @hasraw - 'so they are conservative'
+// @!hasraw foo/struct.Plain.html 'so they are conservative'
+pub struct Generic<T>(pub Vec<T>);
+
+// @has foo/struct.GenericUnfinalized.html
+// @hasraw - 'No finalizer needed:'
+// @!hasraw - 'May need a finalizer:'
+pub struct GenericUnfinalized<T: Copy>(pub T);
@@ -0,0 +1,125 @@
+//@ run-pass
+//! Test that users can query GC properties of types and instances through stable mir.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::mono::Instance;
+use stable_mir::mir::TerminatorKind;
+use stable_mir::ty::{RigidTy, TyKind};
+use stable_mir::CrateDef;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    let locals = items.iter().find(|item| item.name() == "locals").unwrap();
+    let body = locals.body();
+    // Local 0 is the return place.
+    let [gc, vec, has_drop, int, reference] =
+        [1, 2, 3, 4, 5].map(|local| body.locals()[local].ty);
+
+    assert!(gc.is_gc());
+    assert!(!vec.is_gc());
+
+    assert!(!vec.needs_finalizer());
+    assert!(has_drop.needs_finalizer());
+    assert!(!int.needs_finalizer());
+
+    assert!(!gc.is_finalizer_safe());
+    assert!(!reference.is_finalizer_safe());
+    assert!(int.is_finalizer_safe());
+
+    assert!(vec.drop_method_finalizer_elidable());
+    assert!(!has_drop.drop_method_finalizer_elidable());
+
+    let calls = |name: &str| -> Vec<Instance> {
+        let item = items.iter().find(|item| item.name() == name).unwrap();
+        item.body()
+            .blocks
+            .iter()
+            .filter_map(|bb| match &bb.terminator.kind {
+                TerminatorKind::Call { func, .. } => {
+                    let TyKind::RigidTy(RigidTy::FnDef(def, args)) =
+                        func.ty(item.body().locals()).unwrap().kind()
+                    else {
+                        return None;
+                    };
+                    Some(Instance::resolve(def, &args).unwrap())
+                }
+                _ => None,
+            })
+            .collect()
+    };
+    let (entry_points, others): (Vec<_>, Vec<_>) =
+        calls("make").into_iter().partition(|instance| instance.is_fsa_entry_point());
+    assert_eq!(entry_points.len(), 1, "Expected only `Gc::new`, but found: {entry_points:?}");
+    assert!(!others.is_empty());
+
+    // `Default::default` reaches `Gc::new` without being annotated itself, so FSA checks it too.
+    let default_calls = calls("make_default");
+    assert_eq!(default_calls.len(), 1);
+    assert!(default_calls[0].is_fsa_entry_point());
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "gc_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+        #![feature(gc)]
+        use std::gc::Gc;
+
+        pub struct HasDrop(u8);
+
+        impl Drop for HasDrop {{
+            fn drop(&mut self) {{}}
+        }}
+
+        pub fn locals(_a: Gc<u8>, _b: Vec<u8>, _c: HasDrop, _d: u8, _e: &u8) {{}}
+
+        pub fn make() -> Gc<Vec<u8>> {{
+            Gc::new(Vec::new())
+        }}
+
+        pub fn make_default() -> Gc<Vec<u8>> {{
+            Default::default()
+        }}
+        "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,87 @@
+//@ run-pass
+// Buffers whose element type can never hold a pointer are allocated with
+// `GlobalAlloc::alloc_atomic`, so that a collector need not scan them.
+#![feature(gc)]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::mem::{self, MaybeUninit};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ATOMIC: AtomicUsize = AtomicUsize::new(0);
+
+struct Counting;
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn alloc_atomic(&self, layout: Layout) -> *mut u8 {
+        ATOMIC.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+}
+
+#[global_allocator]
+static A: Counting = Counting;
+
+#[allow(dead_code)]
+struct Pixel {
+    rgb: [u8; 3],
+    depth: f32,
+}
+
+fn atomic_allocs<T>(f: impl FnOnce() -> T) -> usize {
+    let before = ATOMIC.load(Ordering::Relaxed);
+    let value = f();
+    let after = ATOMIC.load(Ordering::Relaxed);
+    drop(value);
+    after - before
+}
+
+fn main() {
+    assert!(!mem::may_contain_pointers::<Pixel>());
+    assert!(mem::may_contain_pointers::<usize>());
+    assert!(!mem::may_contain_pointers::<u16>());
+    assert_eq!(mem::may_contain_pointers::<u64>(), mem::size_of::<usize>() <= 8);
+    assert!(mem::may_contain_pointers::<MaybeUninit<u8>>());
+
+    assert_eq!(atomic_allocs(|| Vec::<u8>::with_capacity(64)), 1);
+    assert_eq!(atomic_allocs(|| vec![1.0f64, 2.0, 3.0]), 1);
+    assert_eq!(atomic_allocs(|| String::from("no pointers here")), 1);
+    assert_eq!(atomic_allocs(|| Box::new(Pixel { rgb: [0; 3], depth: 1.0 })), 1);
+    assert_eq!(
+        atomic_allocs(|| {
+            let mut v = Vec::new();
+            v.push(1u32);
+            v
+        }),
+        1
+    );
+
+    assert_eq!(atomic_allocs(|| Vec::<&u8>::with_capacity(64)), 0);
+    assert_eq!(atomic_allocs(|| Vec::<usize>::with_capacity(64)), 0);
+    assert_eq!(atomic_allocs(|| Vec::<MaybeUninit<u8>>::with_capacity(64)), 0);
+    assert_eq!(atomic_allocs(|| Box::new(Box::new(1u8))), 1);
+
+    // In-place collection must not hand an unscanned buffer to elements which may hold
+    // pointers.
+    let v: Vec<u32> = (0..16).collect();
+    let src = v.as_ptr() as usize;
+    let floats: Vec<f32> = v.into_iter().map(|x| x as f32).collect();
+    assert_eq!(floats.as_ptr() as usize, src);
+    let floats: Vec<f64> = floats.into_iter().map(f64::from).collect();
+    let src = floats.as_ptr() as usize;
+    let words: Vec<usize> = floats.into_iter().map(|x| x as usize).collect();
+    assert_ne!(words.as_ptr() as usize, src);
+
+    // A pointer-free buffer may still be reused for pointers through `from_raw_parts`.
+    let mut floats = mem::ManuallyDrop::new(vec![0.0f32; 4]);
+    let (ptr, len, cap) = (floats.as_mut_ptr(), floats.len(), floats.capacity());
+    let words = unsafe { Vec::from_raw_parts(ptr as *mut u32, len, cap) };
+    assert_eq!(words, [0; 4]);
+}
@@ -0,0 +1,112 @@
+//@ run-pass
+//@ compile-flags: -O
+//@ needs-unwind
+// ignore-tidy-linelength
+#![feature(gc)]
+#![allow(dead_code)]
+
+// Checks that moving premature finalization barriers to the end of each `Gc`'s
+// live range still keeps the referent alive for as long as anything derived
+// from the `Gc` is in use.
+
+use std::gc::{Gc, GcAllocator};
+use std::hint::black_box;
+use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time;
+
+const COUNT: usize = 8;
+
+const NOT_FINALIZED: AtomicBool = AtomicBool::new(false);
+static FINALIZED: [AtomicBool; COUNT] = [NOT_FINALIZED; COUNT];
+
+struct Tracked {
+    id: usize,
+    value: u64,
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        FINALIZED[self.id].store(true, Ordering::SeqCst);
+    }
+}
+
+/// Collects and gives the finalizer thread a chance to run, then checks that
+/// the object behind `r` has not been finalized.
+#[inline(never)]
+fn collect_and_check(r: &Tracked) {
+    GcAllocator::force_gc();
+    thread::sleep(time::Duration::from_millis(10));
+    assert!(!FINALIZED[r.id].load(Ordering::SeqCst));
+    assert_eq!(r.value, r.id as u64 * 10);
+}
+
+fn tracked(id: usize) -> Gc<Tracked> {
+    Gc::new(Tracked { id, value: id as u64 * 10 })
+}
+
+/// A reference into the referent stays live across every iteration of a loop.
+fn derived_across_loop() {
+    let gc = tracked(0);
+    let r = &*gc;
+    for _ in 0..3 {
+        collect_and_check(black_box(r));
+    }
+}
+
+/// A new `Gc` each iteration, used through a reference which dies inside it.
+fn gc_per_iteration() {
+    for id in 1..3 {
+        let gc = tracked(id);
+        let r = &gc.value;
+        collect_and_check(&gc);
+        assert_eq!(*black_box(r), id as u64 * 10);
+    }
+}
+
+#[inline(never)]
+fn identity(r: &Tracked) -> &Tracked {
+    black_box(r)
+}
+
+/// References escape into a call, and come back out of one.
+fn derived_escapes_into_call() {
+    let gc = tracked(3);
+    let r = identity(&gc);
+    collect_and_check(r);
+    collect_and_check(identity(r));
+}
+
+#[inline(never)]
+fn check_then_panic(r: &Tracked) {
+    collect_and_check(r);
+    panic!("unwinding past the Gc");
+}
+
+/// The `Gc` is still live on the unwind path out of a call.
+fn derived_live_on_unwind() {
+    let result = panic::catch_unwind(|| {
+        let gc = tracked(4);
+        let r = &*gc;
+        check_then_panic(black_box(r));
+    });
+    assert!(result.is_err());
+}
+
+/// The only use of the `Gc` after the collection is through an integer address.
+fn derived_through_usize() {
+    let gc = tracked(5);
+    let addr = Gc::as_ptr(&gc) as usize;
+    collect_and_check(black_box(unsafe { &*(addr as *const Tracked) }));
+    collect_and_check(unsafe { &*(black_box(addr) as *const Tracked) });
+}
+
+fn main() {
+    panic::set_hook(Box::new(|_| {}));
+    derived_across_loop();
+    gc_per_iteration();
+    derived_escapes_into_call();
+    derived_live_on_unwind();
+    derived_through_usize();
+}
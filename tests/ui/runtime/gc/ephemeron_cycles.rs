@@ -0,0 +1,55 @@
+//@ run-pass
+// An `EphemeronMap` entry must keep its value alive only while its key is reachable, even when
+// the value refers back to the key.
+#![feature(gc)]
+#![feature(allocator_api)]
+#![allow(dead_code)]
+
+use std::gc::{EphemeronMap, Gc, GcAllocator};
+
+static ENTRIES: usize = 1000;
+static ROUNDS: usize = 10;
+
+struct Key(usize);
+
+struct Value {
+    key: Gc<Key>,
+    payload: [usize; 8],
+}
+
+fn fill(map: &mut EphemeronMap<Key, Value>, kept: &mut Vec<Gc<Key>, GcAllocator>) {
+    for i in 0..ENTRIES {
+        let key = Gc::new(Key(i));
+        map.insert(key, Gc::new(Value { key, payload: [i; 8] }));
+        if i % 10 == 0 {
+            kept.push(key);
+        }
+    }
+}
+
+fn main() {
+    let mut map = EphemeronMap::new();
+    // The kept keys must be in memory the collector scans, or it could not see them.
+    let mut kept = Vec::new_in(GcAllocator);
+    for _ in 0..ROUNDS {
+        fill(&mut map, &mut kept);
+        GcAllocator::force_gc();
+
+        // Every entry whose key is still reachable must survive, along with its value.
+        for key in &kept {
+            let value = map.get(key).unwrap();
+            assert!(Gc::ptr_eq(&value.key, key));
+            assert_eq!(value.payload, [key.0; 8]);
+        }
+    }
+
+    // The cyclic entries whose keys were dropped must be collected. The collector is
+    // conservative, so a handful may be kept alive by stale words on the stack.
+    let live = map.len();
+    assert!(live >= kept.len());
+    assert!(live < kept.len() + ENTRIES, "{live} entries survived");
+
+    let key = kept.pop().unwrap();
+    assert!(map.remove(&key).is_some());
+    assert!(!map.contains_key(&key));
+}
@@ -0,0 +1,43 @@
+//@ run-pass
+//@ no-prefer-dynamic
+// ignore-tidy-linelength
+#![feature(gc)]
+#![allow(dead_code)]
+
+use std::gc::{Gc, GcAllocator};
+use std::sync::Arc;
+use std::thread;
+use std::time;
+
+#[global_allocator]
+static A: GcAllocator = GcAllocator;
+
+static ALLOCATED_COUNT: usize = 10;
+static SLEEP_MAX: u64 = 1024; // in millis.
+
+fn foo(shared: &Arc<usize>) {
+    for _ in 0..ALLOCATED_COUNT {
+        let mut _gc = Some(Gc::new(Arc::clone(shared)));
+
+        // Zero the root to the GC object.
+        _gc = None;
+    }
+}
+
+fn main() {
+    let shared = Arc::new(0);
+    foo(&shared);
+    GcAllocator::force_gc();
+
+    // Give the finalizer thread time to run any finalizers that were wrongly
+    // registered.
+    let mut sleep_duration = 2;
+    while sleep_duration <= SLEEP_MAX {
+        thread::sleep(time::Duration::from_millis(sleep_duration));
+        sleep_duration = sleep_duration * 2;
+    }
+
+    // With `GcAllocator` as the global allocator, `Arc`'s drop method is elided,
+    // so the strong count is never decremented.
+    assert_eq!(Arc::strong_count(&shared), ALLOCATED_COUNT + 1);
+}
@@ -0,0 +1,45 @@
+//@ run-pass
+// `Gc::free` must run a registered finalizer synchronously, on the calling thread, exactly once.
+#![feature(gc)]
+
+use std::gc::{Gc, GcAllocator};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+static DROPPED: AtomicUsize = AtomicUsize::new(0);
+// Set by `main` only for the duration of each `Gc::free` call.
+static FREEING: AtomicBool = AtomicBool::new(false);
+static DROPPED_OUTSIDE_FREE: AtomicUsize = AtomicUsize::new(0);
+
+struct Scratch([usize; 16]);
+
+impl Drop for Scratch {
+    fn drop(&mut self) {
+        if !FREEING.load(Ordering::SeqCst) {
+            DROPPED_OUTSIDE_FREE.fetch_add(1, Ordering::SeqCst);
+        }
+        DROPPED.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn main() {
+    for i in 0..100 {
+        let node = Gc::new(Scratch([i; 16]));
+        assert_eq!(node.0[15], i);
+        FREEING.store(true, Ordering::SeqCst);
+        unsafe { Gc::free(node) };
+        FREEING.store(false, Ordering::SeqCst);
+        // The finalizer has already run by the time `free` returns, so it ran on this thread
+        // while it was blocked in the call.
+        assert_eq!(DROPPED.load(Ordering::SeqCst), i + 1);
+    }
+
+    // Values without a finalizer, including zero-sized ones, can be freed too.
+    unsafe { Gc::free(Gc::new([1u64; 32])) };
+    unsafe { Gc::free(Gc::new(())) };
+
+    // The finalizers were unregistered, so a collection must not run them again.
+    GcAllocator::force_gc();
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    assert_eq!(DROPPED.load(Ordering::SeqCst), 100);
+    assert_eq!(DROPPED_OUTSIDE_FREE.load(Ordering::SeqCst), 0);
+}
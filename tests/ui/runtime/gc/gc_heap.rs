@@ -0,0 +1,75 @@
+//@ run-pass
+//@ ignore-emscripten no threads support
+// Objects in a `GcHeap` are collected when unreachable, are counted separately from other heaps,
+// and are all freed when their heap is dropped.
+#![feature(gc)]
+
+use std::gc::{Gc, GcAllocator, GcHeap, HeapGc};
+use std::hint::black_box;
+use std::thread;
+
+struct Node<'h> {
+    value: usize,
+    shared: Gc<usize>,
+    next: Option<HeapGc<'h, Node<'h>>>,
+}
+
+#[inline(never)]
+fn build<'h>(heap: &'h GcHeap, shared: Gc<usize>, len: usize) -> HeapGc<'h, Node<'h>> {
+    let mut head = heap.alloc(Node { value: 0, shared, next: None });
+    for value in 1..len {
+        head = heap.alloc(Node { value, shared, next: Some(head) });
+    }
+    head
+}
+
+#[inline(never)]
+fn garbage(heap: &GcHeap, n: usize) {
+    for i in 0..n {
+        black_box(heap.alloc([i; 4]));
+    }
+}
+
+fn sum(mut node: Option<HeapGc<'_, Node<'_>>>) -> usize {
+    let mut total = 0;
+    while let Some(n) = node {
+        assert_eq!(*n.shared, 42);
+        total += n.value;
+        node = n.next;
+    }
+    total
+}
+
+fn main() {
+    let shared = Gc::new(42);
+    let kept = GcHeap::new();
+    let scratch = GcHeap::new();
+
+    let list = build(&kept, shared, 100);
+    garbage(&scratch, 10_000);
+    GcAllocator::force_gc();
+
+    assert_eq!(sum(Some(list)), 99 * 100 / 2);
+    let stats = kept.stats();
+    assert_eq!(stats.allocated_objects, 100);
+    assert!(stats.live_objects >= 100);
+    let stats = scratch.stats();
+    assert_eq!(stats.allocated_objects, 10_000);
+    assert!(stats.live_objects < 10_000);
+
+    // Heaps can be used from several threads at once.
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                let list = build(&kept, shared, 10);
+                garbage(&scratch, 1_000);
+                assert_eq!(sum(Some(list)), 45);
+            });
+        }
+    });
+    assert_eq!(kept.stats().allocated_objects, 140);
+
+    drop(scratch);
+    drop(kept);
+    assert_eq!(*shared, 42);
+}
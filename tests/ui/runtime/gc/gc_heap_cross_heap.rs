@@ -0,0 +1,30 @@
+//@ run-pass
+//@ needs-unwind
+//@ only-debug
+// When the standard library has debug assertions, `GcHeap::alloc` rejects values which hold a
+// pointer to an object in another heap.
+#![feature(gc)]
+
+use std::gc::{Gc, GcHeap, HeapGc};
+use std::panic::{self, AssertUnwindSafe};
+
+fn rejects(f: impl FnOnce()) -> bool {
+    panic::catch_unwind(AssertUnwindSafe(f)).is_err()
+}
+
+fn main() {
+    panic::set_hook(Box::new(|_| {}));
+    let a = GcHeap::new();
+    let b = GcHeap::new();
+    let other: HeapGc<'_, usize> = b.alloc(1);
+    let own: HeapGc<'_, usize> = a.alloc(2);
+
+    assert!(rejects(|| drop(a.alloc(other))));
+    assert!(rejects(|| drop(a.alloc((own, 7u64, other)))));
+    assert!(rejects(|| drop(a.alloc([own, own, other]))));
+
+    // References within a heap, and to objects outside every heap, are fine.
+    drop(a.alloc(own));
+    drop(a.alloc([own, own]));
+    drop(a.alloc(Gc::new(3)));
+}
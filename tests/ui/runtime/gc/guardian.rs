@@ -0,0 +1,55 @@
+//@ run-pass
+// Values registered with a `Guardian` are dropped by the thread which polls it, not by the
+// finalizer thread, so they may be `!Send`.
+#![feature(gc)]
+#![allow(dead_code)]
+
+use std::cell::Cell;
+use std::gc::{GcAllocator, Guardian};
+use std::rc::Rc;
+use std::thread;
+use std::time;
+
+static ALLOCATED_COUNT: usize = 10;
+static SLEEP_MAX: u64 = 8192; // in millis.
+
+thread_local! {
+    static DROPPED_ON_OWNER: Cell<usize> = Cell::new(0);
+}
+
+struct ThreadAffine(Rc<usize>);
+
+impl Drop for ThreadAffine {
+    fn drop(&mut self) {
+        DROPPED_ON_OWNER.with(|c| c.set(c.get() + 1));
+    }
+}
+
+fn register(guardian: &Guardian<ThreadAffine>) {
+    for i in 0..ALLOCATED_COUNT {
+        let mut _gc = Some(guardian.register(ThreadAffine(Rc::new(i))));
+        // Zero the root to the GC object.
+        _gc = None;
+    }
+}
+
+fn main() {
+    let guardian = Guardian::new();
+    register(&guardian);
+    GcAllocator::force_gc();
+
+    let mut count = 0;
+    let mut sleep_duration = 2;
+    while count < ALLOCATED_COUNT - 1 && sleep_duration <= SLEEP_MAX {
+        // Wait an acceptable amount of time for the finalizer thread to queue the values.
+        thread::sleep(time::Duration::from_millis(sleep_duration));
+        sleep_duration = sleep_duration * 2;
+        count += guardian.poll();
+    }
+
+    // On some platforms, the last object might not be queued because it's kept alive by a
+    // lingering reference.
+    assert!(count >= ALLOCATED_COUNT - 1);
+    assert!(count <= ALLOCATED_COUNT);
+    assert_eq!(DROPPED_ON_OWNER.with(|c| c.get()), count);
+}
@@ -0,0 +1,48 @@
+//@ run-pass
+//@ ignore-emscripten no threads support
+// Small objects are allocated from per-thread free lists. Objects handed out from them must stay
+// alive across collections, and must not be handed out twice, while threads come and go.
+#![feature(gc)]
+
+use std::gc::{Gc, GcAllocator};
+use std::thread;
+
+static THREADS: usize = 8;
+static NODES: usize = 10_000;
+
+struct Node {
+    next: Option<Gc<Node>>,
+    value: usize,
+}
+
+fn build(tag: usize) -> Option<Gc<Node>> {
+    let mut list = None;
+    for i in 0..NODES {
+        list = Some(Gc::new(Node { next: list, value: tag * NODES + i }));
+        if i % 1000 == 0 {
+            GcAllocator::force_gc();
+        }
+    }
+    list
+}
+
+fn check(tag: usize, mut list: Option<Gc<Node>>) {
+    for i in (0..NODES).rev() {
+        let node = list.unwrap();
+        assert_eq!(node.value, tag * NODES + i);
+        list = node.next;
+    }
+    assert!(list.is_none());
+}
+
+fn main() {
+    let handles: Vec<_> = (0..THREADS)
+        .map(|tag| thread::spawn(move || check(tag, build(tag))))
+        .collect();
+    let local = build(THREADS);
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    GcAllocator::force_gc();
+    check(THREADS, local);
+}
@@ -0,0 +1,82 @@
+//@ run-pass
+// With incremental collection enabled, objects which are only reachable through pointers stored
+// into the heap after they were first scanned must not be collected. Each kind of store below is
+// one the compiler has to put a write barrier after.
+//
+// CI also runs this with std built with `alloy.gc-write-barriers` and
+// `ALLOY_EXPECT_INCREMENTAL` set, where the collector must really be incremental.
+#![feature(gc)]
+#![feature(allocator_api)]
+
+use std::cell::{Cell, RefCell, UnsafeCell};
+use std::gc::{self, Gc, GcAllocator};
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+static FINALIZED: AtomicUsize = AtomicUsize::new(0);
+
+struct Leaf(usize);
+
+impl Drop for Leaf {
+    fn drop(&mut self) {
+        FINALIZED.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+struct Holder {
+    cell: Cell<Option<Gc<Leaf>>>,
+    refcell: RefCell<Vec<Gc<Leaf>, GcAllocator>>,
+    mutex: Mutex<Option<Gc<Leaf>>>,
+    atomic: AtomicPtr<Leaf>,
+    raw: UnsafeCell<[Option<Gc<Leaf>>; 2]>,
+}
+
+#[inline(never)]
+fn churn() {
+    for i in 0..10_000 {
+        std::hint::black_box(Gc::new([i; 8]));
+    }
+}
+
+fn main() {
+    let incremental = unsafe { gc::enable_incremental() };
+    if std::env::var_os("ALLOY_EXPECT_INCREMENTAL").is_some() {
+        assert!(incremental);
+    }
+
+    let holder = Gc::new(Holder {
+        cell: Cell::new(None),
+        refcell: RefCell::new(Vec::new_in(GcAllocator)),
+        mutex: Mutex::new(None),
+        atomic: AtomicPtr::new(ptr::null_mut()),
+        raw: UnsafeCell::new([None, None]),
+    });
+    churn();
+
+    // Only the heap refers to each leaf once its store is done.
+    let mut next = 0;
+    let mut leaf = || {
+        next += 1;
+        Gc::new(Leaf(next))
+    };
+    for _ in 0..100 {
+        holder.cell.set(Some(leaf()));
+        holder.refcell.borrow_mut().push(leaf());
+        *holder.mutex.lock().unwrap() = Some(leaf());
+        holder.atomic.store(Gc::as_ptr(&leaf()) as *mut Leaf, Ordering::SeqCst);
+        unsafe { holder.raw.get().write([Some(leaf()), Some(leaf())]) };
+        churn();
+    }
+    GcAllocator::force_gc();
+
+    // Everything in the refcell and the last value of every other field is still reachable.
+    assert!(holder.cell.take().is_some_and(|leaf| leaf.0 > 0));
+    assert_eq!(holder.refcell.borrow().len(), 100);
+    assert!(holder.refcell.borrow().iter().enumerate().all(|(i, leaf)| leaf.0 == 6 * i + 2));
+    assert!(holder.mutex.lock().unwrap().is_some_and(|leaf| leaf.0 > 0));
+    assert_eq!(unsafe { (*holder.atomic.load(Ordering::SeqCst)).0 }, 600 - 2);
+    assert!(unsafe { &*holder.raw.get() }.iter().all(|leaf| leaf.is_some_and(|leaf| leaf.0 > 0)));
+    // At most the overwritten leaves can have been finalized.
+    assert!(FINALIZED.load(Ordering::Relaxed) <= 600 - 100 - 5);
+}
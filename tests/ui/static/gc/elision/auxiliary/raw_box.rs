@@ -0,0 +1,36 @@
+#![feature(gc)]
+
+use std::alloc::{alloc, dealloc, Layout};
+use std::mem::needs_finalizer;
+use std::ptr;
+
+// This crate can't see `GcAllocator`, which the crate using it declares as
+// the global allocator.
+pub struct RawBox<T> {
+    ptr: *mut T,
+}
+
+impl<T> RawBox<T> {
+    pub fn new(value: T) -> Self {
+        unsafe {
+            let ptr = alloc(Layout::new::<T>()) as *mut T;
+            ptr::write(ptr, value);
+            RawBox { ptr }
+        }
+    }
+}
+
+impl<T> Drop for RawBox<T> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::drop_in_place(self.ptr);
+            dealloc(self.ptr as *mut u8, Layout::new::<T>());
+        }
+    }
+}
+
+pub static RAW_BOX_USIZE: bool = needs_finalizer::<RawBox<usize>>();
+
+pub fn raw_box_needs_finalizer<T>() -> bool {
+    needs_finalizer::<RawBox<T>>()
+}
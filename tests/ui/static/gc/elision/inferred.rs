@@ -0,0 +1,111 @@
+//@ run-pass
+//@ no-prefer-dynamic
+// ignore-tidy-linelength
+#![feature(gc)]
+#![allow(dead_code)]
+include!{"./auxiliary/types.rs"}
+
+use std::alloc::{alloc, dealloc, Layout};
+use std::gc::{Gc, GcAllocator};
+use std::mem::needs_finalizer;
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[global_allocator]
+static A: GcAllocator = GcAllocator;
+
+static DROP_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+// Only drops its contents and frees memory, so it should be inferred as
+// finalizer elidable without implementing `DropMethodFinalizerElidable`.
+struct RawBox<T> {
+    ptr: *mut T,
+}
+
+impl<T> RawBox<T> {
+    fn new(value: T) -> Self {
+        unsafe {
+            let ptr = alloc(Layout::new::<T>()) as *mut T;
+            ptr::write(ptr, value);
+            RawBox { ptr }
+        }
+    }
+}
+
+impl<T> Drop for RawBox<T> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::drop_in_place(self.ptr);
+            dealloc(self.ptr as *mut u8, Layout::new::<T>());
+        }
+    }
+}
+
+// Writes to memory it doesn't own, so must still be finalized.
+struct Counted;
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        DROP_COUNT.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+// Resets its own fields before freeing, which is still only deallocation.
+struct ClearOnDrop {
+    ptr: *mut u8,
+    len: usize,
+}
+
+impl Drop for ClearOnDrop {
+    fn drop(&mut self) {
+        unsafe { dealloc(self.ptr, Layout::array::<u8>(self.len).unwrap()) };
+        self.len = 0;
+    }
+}
+
+// Frees memory, but may exit the process first.
+struct ExitOnDrop {
+    ptr: *mut u8,
+    exit: bool,
+}
+
+impl Drop for ExitOnDrop {
+    fn drop(&mut self) {
+        if self.exit {
+            std::process::exit(1);
+        }
+        unsafe { dealloc(self.ptr, Layout::new::<u8>()) };
+    }
+}
+
+static RAW_BOX_TRIVIAL: bool = needs_finalizer::<RawBox<usize>>();
+static RAW_BOX_NON_ANNOTATED: bool = needs_finalizer::<RawBox<NonAnnotated>>();
+static RAW_BOX_UNFINALIZABLE: bool = needs_finalizer::<RawBox<HasDropNoFinalize>>();
+static RAW_BOX_COUNTED: bool = needs_finalizer::<RawBox<Counted>>();
+static RAW_BOX_NESTED_COUNTED: bool = needs_finalizer::<RawBox<RawBox<Counted>>>();
+static VEC_RAW_BOX: bool = needs_finalizer::<Vec<RawBox<usize>>>();
+static COUNTED: bool = needs_finalizer::<Counted>();
+static CLEAR_ON_DROP: bool = needs_finalizer::<ClearOnDrop>();
+static EXIT_ON_DROP: bool = needs_finalizer::<ExitOnDrop>();
+// Empty drop methods don't deallocate anything, so aren't inferred.
+static HAS_DROP: bool = needs_finalizer::<HasDrop>();
+
+fn main() {
+    assert!(!RAW_BOX_TRIVIAL);
+    assert!(!RAW_BOX_NON_ANNOTATED);
+    assert!(!RAW_BOX_UNFINALIZABLE);
+    assert!(RAW_BOX_COUNTED);
+    assert!(RAW_BOX_NESTED_COUNTED);
+    assert!(!VEC_RAW_BOX);
+    assert!(COUNTED);
+    assert!(!CLEAR_ON_DROP);
+    assert!(EXIT_ON_DROP);
+    assert!(HAS_DROP);
+
+    drop(RawBox::new(Counted));
+    assert_eq!(DROP_COUNT.load(Ordering::Relaxed), 1);
+
+    // No finalizer is registered, so finalizer safety analysis has nothing to
+    // check in the drop method.
+    let _ = Gc::new(RawBox::new(1usize));
+}
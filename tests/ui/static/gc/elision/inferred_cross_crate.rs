@@ -0,0 +1,36 @@
+//@ run-pass
+//@ no-prefer-dynamic
+//@ aux-build:raw_box.rs
+// ignore-tidy-linelength
+#![feature(gc)]
+#![allow(dead_code)]
+include!{"./auxiliary/types.rs"}
+
+extern crate raw_box;
+
+use raw_box::RawBox;
+use std::gc::{Gc, GcAllocator};
+use std::mem::needs_finalizer;
+
+#[global_allocator]
+static A: GcAllocator = GcAllocator;
+
+// Whether a drop method only deallocates must not depend on the global
+// allocator each crate can see, or the two crates would disagree about
+// whether `RawBox` needs finalizing.
+static RAW_BOX_USIZE: bool = needs_finalizer::<RawBox<usize>>();
+static RAW_BOX_UNFINALIZABLE: bool = needs_finalizer::<RawBox<HasDropNoFinalize>>();
+static RAW_BOX_FINALIZABLE: bool = needs_finalizer::<RawBox<HasDrop>>();
+
+fn main() {
+    assert!(!raw_box::RAW_BOX_USIZE);
+    assert_eq!(raw_box::RAW_BOX_USIZE, RAW_BOX_USIZE);
+    assert!(!raw_box::raw_box_needs_finalizer::<usize>());
+    assert!(!RAW_BOX_UNFINALIZABLE);
+    assert!(RAW_BOX_FINALIZABLE);
+    assert_eq!(raw_box::raw_box_needs_finalizer::<HasDrop>(), RAW_BOX_FINALIZABLE);
+
+    // Finalizer safety analysis uses the same answer, so this isn't rejected
+    // for dereferencing a raw pointer in the drop method.
+    let _ = Gc::new(RawBox::new(1usize));
+}
@@ -0,0 +1,66 @@
+//@ run-pass
+//@ no-prefer-dynamic
+// ignore-tidy-linelength
+#![feature(allocator_api)]
+#![feature(core_intrinsics)]
+#![feature(gc)]
+#![allow(dead_code)]
+include!{"./auxiliary/types.rs"}
+
+use std::alloc::System;
+use std::cell::RefCell;
+use std::gc::GcAllocator;
+use std::intrinsics::needs_finalizer_with_gc_allocator as needs_finalizer;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+#[global_allocator]
+static A: GcAllocator = GcAllocator;
+
+static RC: bool = needs_finalizer::<Rc<HasDrop>>();
+static RC_NO_FINALIZE: bool = needs_finalizer::<Rc<HasDropNoFinalize>>();
+static RC_USIZE: bool = needs_finalizer::<Rc<usize>>();
+static RC_STRING: bool = needs_finalizer::<Rc<String>>();
+static RC_SLICE: bool = needs_finalizer::<Rc<[HasDropNoFinalize]>>();
+static RC_REFCELL_NO_FINALIZE: bool = needs_finalizer::<Rc<RefCell<HasDropNoFinalize>>>();
+static RC_NESTED: bool = needs_finalizer::<Rc<Rc<HasDrop>>>();
+static RC_SYSTEM: bool = needs_finalizer::<Rc<usize, System>>();
+
+static ARC: bool = needs_finalizer::<Arc<HasDrop>>();
+static ARC_NO_FINALIZE: bool = needs_finalizer::<Arc<HasDropNoFinalize>>();
+static ARC_MUTEX_NO_FINALIZE: bool = needs_finalizer::<Arc<Mutex<HasDropNoFinalize>>>();
+static ARC_VEC: bool = needs_finalizer::<Arc<Vec<u8>>>();
+static ARC_NESTED: bool = needs_finalizer::<Arc<Arc<HasDrop>>>();
+static ARC_SYSTEM: bool = needs_finalizer::<Arc<usize, System>>();
+
+static CONTAINS_RC: bool = needs_finalizer::<MaybeFinalize<Rc<HasDropNoFinalize>>>();
+static CONTAINS_ARC: bool = needs_finalizer::<(usize, Arc<HasDrop>)>();
+
+// `Gc::new` checks for `GcAllocator` at run-time, so `mem::needs_finalizer`
+// doesn't depend on it.
+static RC_ANY_ALLOCATOR: bool = std::mem::needs_finalizer::<Rc<HasDropNoFinalize>>();
+static ARC_ANY_ALLOCATOR: bool = std::mem::needs_finalizer::<Arc<HasDropNoFinalize>>();
+
+fn main() {
+    assert!(RC);
+    assert!(!RC_NO_FINALIZE);
+    assert!(!RC_USIZE);
+    assert!(!RC_STRING);
+    assert!(!RC_SLICE);
+    assert!(!RC_REFCELL_NO_FINALIZE);
+    assert!(RC_NESTED);
+    assert!(RC_SYSTEM);
+
+    assert!(ARC);
+    assert!(!ARC_NO_FINALIZE);
+    assert!(!ARC_MUTEX_NO_FINALIZE);
+    assert!(!ARC_VEC);
+    assert!(ARC_NESTED);
+    assert!(ARC_SYSTEM);
+
+    assert!(!CONTAINS_RC);
+    assert!(CONTAINS_ARC);
+
+    assert!(RC_ANY_ALLOCATOR);
+    assert!(ARC_ANY_ALLOCATOR);
+}
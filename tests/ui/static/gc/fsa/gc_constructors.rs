@@ -0,0 +1,37 @@
+#![feature(gc)]
+#![feature(negative_impls)]
+#![allow(dead_code)]
+include!{"./auxiliary/types.rs"}
+
+impl<'a> Drop for HasRef<'a> {
+    fn drop(&mut self) {
+        use_val(self.a); // should fail
+    }
+}
+
+fn main() {
+    let _: Gc<HasRef> = Default::default();
+    //~^ ERROR: The drop method for `HasRef<'_>` cannot be safely finalized.
+    let _: Gc<[HasRef]> = Gc::from([HasRef::default()]);
+    //~^ ERROR: The drop method for `HasRef<'_>` cannot be safely finalized.
+
+    // Cloning a `Gc` doesn't register another finalizer, so only the
+    // construction is checked.
+    let gc = Gc::new(HasRef::default());
+    //~^ ERROR: The drop method for `HasRef<'_>` cannot be safely finalized.
+    let _ = gc.clone();
+
+    let _: Gc<[HasRef]> = std::iter::once(HasRef::default()).collect();
+    //~^ ERROR: The drop method for `HasRef<'_>` cannot be safely finalized.
+
+    // The following should all pass.
+    let _: Gc<u8> = Default::default();
+    let _: Gc<[u8]> = Gc::from([1, 2, 3]);
+    let _: Gc<[u8]> = (1..4).collect();
+    let _ = Gc::new(1).clone();
+
+    // These constructors never register a finalizer which FSA has to check.
+    let _ = Gc::new_unfinalizable(HasRef::default());
+    let _ = unsafe { Gc::new_unsynchronised(HasRef::default()) };
+    let _ = std::gc::Guardian::<HasRef>::new();
+}
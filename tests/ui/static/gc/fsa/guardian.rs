@@ -0,0 +1,38 @@
+#![feature(gc)]
+#![feature(negative_impls)]
+#![allow(dead_code)]
+#![allow(unused_variables)]
+include!{"./auxiliary/types.rs"}
+
+use std::cell::Cell;
+use std::gc::Guardian;
+use std::rc::Rc;
+
+thread_local! {
+    static COUNTER: Cell<u32> = Cell::new(0);
+}
+
+struct ThreadAffine(Rc<Cell<u32>>);
+
+impl Drop for ThreadAffine {
+    fn drop(&mut self) {
+        // A guardian drops this on the thread which polls it, so neither of these are errors.
+        self.0.set(self.0.get() + 1);
+        COUNTER.set(COUNTER.get() + 1);
+    }
+}
+
+impl<'a> Drop for HasRef<'a> {
+    fn drop(&mut self) {
+        use_val(self.a); // should fail
+    }
+}
+
+fn main() {
+    let guardian = Guardian::new();
+    guardian.register(ThreadAffine(Rc::new(Cell::new(0))));
+
+    let guardian = Guardian::new();
+    guardian.register(HasRef::default());
+    //~^ ERROR: The drop method for `HasRef<'_>` cannot be safely finalized.
+}
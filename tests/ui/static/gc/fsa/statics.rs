@@ -0,0 +1,34 @@
+#![feature(gc)]
+#![feature(negative_impls)]
+#![allow(dead_code)]
+#![allow(unused_variables)]
+include!{"./auxiliary/types.rs"}
+
+#[derive(Debug)]
+struct Logger {
+    prefix: &'static str,
+    level: &'static u64,
+}
+
+static LOGGER: Logger = Logger { prefix: "gc", level: &1 };
+static LOGGER_REF: &'static Logger = &LOGGER;
+static LEVELS: [&'static u64; 2] = [&1, &2];
+
+impl<'a> Drop for HasRef<'a> {
+    fn drop(&mut self) {
+        use_val(LOGGER.prefix); // should pass
+        use_val(LOGGER.level); // should pass
+        use_val(LOGGER_REF.level); // should pass
+        use_val(LEVELS[1]); // should pass
+
+        let logger: &'static Logger = &LOGGER;
+        use_val(logger.prefix); // should pass
+
+        use_val(self.a); // should fail
+    }
+}
+
+fn main() {
+    Gc::new(HasRef::default());
+    //~^ ERROR: The drop method for `HasRef<'_>` cannot be safely finalized.
+}
@@ -0,0 +1,40 @@
+#![feature(gc, allocator_api)]
+#![deny(gc_in_unscanned_memory)]
+#![allow(dead_code)]
+
+use std::alloc::System;
+use std::cell::RefCell;
+use std::gc::{Gc, GcAllocator};
+
+fn pass_through(v: Vec<Gc<u8>, System>) -> Vec<Gc<u8>, System> {
+    v
+}
+
+fn main() {
+    let _ = Box::new_in(Gc::new(1), System);
+    //~^ ERROR stores a `Gc` in memory from `System`
+    let mut v = Vec::new_in(System);
+    //~^ ERROR stores a `Gc` in memory from `System`
+    v.push(Gc::new(1u8));
+    let _ = pass_through(v);
+
+    // Memory from these allocators is scanned.
+    let _ = Box::new(Gc::new(1));
+    let _ = Box::new_in(Gc::new(1), GcAllocator);
+    let _ = Vec::<Gc<u8>, _>::with_capacity_in(1, GcAllocator);
+
+    // No `Gc`s, so nothing for the collector to miss.
+    let _ = Box::new_in(1, System);
+}
+
+// Statics are scanned, but memory they own from other allocators is not.
+static mut SLOTS: [Option<Gc<u8>>; 4] = [None; 4];
+static mut UNSCANNED: Vec<Gc<u8>, System> = Vec::new_in(System);
+//~^ ERROR stores a `Gc` in memory from `System`
+
+thread_local! {
+    // Thread locals are scanned, even on targets which fall back to OS keys.
+    static LOCAL: Gc<u8> = Gc::new(1);
+    static LOCAL_UNSCANNED: RefCell<Vec<Gc<u8>, System>> = RefCell::new(Vec::new_in(System));
+    //~^ ERROR stores a `Gc` in memory from `System`
+}
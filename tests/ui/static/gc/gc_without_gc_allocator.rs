@@ -0,0 +1,13 @@
+//@ build-pass
+//@ no-prefer-dynamic
+// ^-- with a dynamically linked std, the allocator shim comes from libstd and is
+// not checked.
+#![feature(gc)]
+
+use std::gc::Gc;
+
+fn main() {
+    // This `Vec` is allocated by `System`, so the collector can't see the `Gc` in it.
+    let v = vec![Gc::new(1)];
+    assert_eq!(*v[0], 1);
+}
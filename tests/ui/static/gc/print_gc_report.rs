@@ -0,0 +1,22 @@
+//@ compile-flags: -Z print-gc-report
+//@ build-pass
+//@ ignore-pass
+// ^-- needed because `--pass check` does not emit the output needed.
+#![feature(gc)]
+#![allow(dead_code)]
+
+use std::gc::Gc;
+
+struct HasDrop(u64);
+
+impl Drop for HasDrop {
+    fn drop(&mut self) {}
+}
+
+struct Elidable(Vec<u64>);
+
+fn main() {
+    let _ = Gc::new(1u64);
+    let _ = Gc::new(HasDrop(1));
+    let _ = Gc::new(Elidable(Vec::new()));
+}
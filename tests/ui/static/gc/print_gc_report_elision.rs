@@ -0,0 +1,28 @@
+//@ compile-flags: -Z print-gc-report
+//@ build-pass
+//@ ignore-pass
+//@ no-prefer-dynamic
+// ^-- needed because `--pass check` does not emit the output needed.
+#![feature(gc)]
+#![allow(dead_code)]
+
+use std::alloc::{dealloc, Layout};
+use std::gc::{Gc, GcAllocator};
+use std::rc::Rc;
+
+#[global_allocator]
+static A: GcAllocator = GcAllocator;
+
+// Inferred to only deallocate, without implementing `DropMethodFinalizerElidable`.
+struct RawBuf(*mut u64);
+
+impl Drop for RawBuf {
+    fn drop(&mut self) {
+        unsafe { dealloc(self.0 as *mut u8, Layout::new::<u64>()) }
+    }
+}
+
+fn main() {
+    let _ = Gc::new(RawBuf(std::ptr::null_mut()));
+    let _ = Gc::new(Rc::new(1u64));
+}